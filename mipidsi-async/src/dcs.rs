@@ -19,6 +19,8 @@ mod set_scroll_start;
 pub use set_scroll_start::*;
 mod set_tearing_effect;
 pub use set_tearing_effect::*;
+mod set_tear_scanline;
+pub use set_tear_scanline::*;
 mod set_invert_mode;
 pub use set_invert_mode::*;
 
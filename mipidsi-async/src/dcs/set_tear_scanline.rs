@@ -0,0 +1,43 @@
+//! Module for the STE tear scanline instruction constructor
+
+use super::DcsCommand;
+
+/// Set Tear Scanline
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SetTearScanline(u16);
+
+impl SetTearScanline {
+    /// Creates a new Set Tear Scanline command.
+    pub const fn new(scanline: u16) -> Self {
+        Self(scanline)
+    }
+}
+
+impl DcsCommand for SetTearScanline {
+    fn instruction(&self) -> u8 {
+        0x44
+    }
+
+    fn fill_params_buf(&self, buffer: &mut [u8]) -> usize {
+        let bytes = self.0.to_be_bytes();
+        buffer[0] = bytes[0];
+        buffer[1] = bytes[1];
+
+        2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ste_fills_scanline_properly() {
+        let ste = SetTearScanline::new(260);
+
+        let mut buffer = [0u8; 2];
+        assert_eq!(ste.instruction(), 0x44);
+        assert_eq!(ste.fill_params_buf(&mut buffer), 2);
+        assert_eq!(buffer, [0x1, 0x04]);
+    }
+}
@@ -110,6 +110,7 @@ pub mod interface;
 
 use embedded_hal::delay::DelayNs;
 use embedded_hal::digital::OutputPin;
+use embedded_hal_async::digital::Wait;
 
 pub mod options;
 use interface::InterfacePixelFormat;
@@ -344,6 +345,26 @@ where
             .write_command(dcs::SetTearingEffect::new(tearing_effect)).await
     }
 
+    ///
+    /// Sets the scanline at which the tearing effect output is triggered.
+    ///
+    pub async fn set_tear_scanline(&mut self, scanline: u16) -> Result<(), DI::Error> {
+        self.di.write_command(dcs::SetTearScanline::new(scanline)).await
+    }
+
+    /// Waits for the start of the next vertical blanking period.
+    ///
+    /// `te` must be connected to the TE output of the display controller and
+    /// the output must be enabled using
+    /// [`set_tearing_effect`](Self::set_tearing_effect) with
+    /// [`TearingEffect::Vertical`](options::TearingEffect::Vertical).
+    /// Drawing operations started directly after this method returns won't be
+    /// visible until the frame is complete, as long as the data is written
+    /// faster than the display is refreshed.
+    pub async fn wait_for_vblank<TE: Wait>(&mut self, te: &mut TE) -> Result<(), TE::Error> {
+        te.wait_for_rising_edge().await
+    }
+
     ///
     /// Returns `true` if display is currently set to sleep.
     ///
@@ -0,0 +1,21 @@
+//! [`DrawTarget`](embedded_graphics_core::draw_target::DrawTarget) adapters.
+//!
+//! The adapters in this module wrap a [`Display`](crate::Display) (or any other
+//! draw target) and change how drawing operations are translated before they
+//! are forwarded to the wrapped target.
+
+mod convert;
+pub use convert::*;
+
+mod dither;
+pub use dither::*;
+
+mod idle;
+pub(crate) use idle::idle_color_bits;
+pub use idle::IdleColorQuantizer;
+
+mod scaled;
+pub use scaled::*;
+
+mod scrolling;
+pub use scrolling::*;
@@ -0,0 +1,244 @@
+use core::marker::PhantomData;
+
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::Dimensions,
+    pixelcolor::{BinaryColor, PixelColor},
+    primitives::Rectangle,
+    Pixel,
+};
+
+/// Draw target adapter which converts colors into the color format of the wrapped target.
+///
+/// [`Display`](crate::Display) only implements [`DrawTarget`] for the color format of the
+/// display model. This adapter can be used to draw widgets and images which use another color
+/// type, like [`Rgb888`](embedded_graphics_core::pixelcolor::Rgb888) or
+/// [`Gray8`](embedded_graphics_core::pixelcolor::Gray8), by converting all colors using the
+/// [`From`] implementations provided by `embedded-graphics`.
+///
+/// `fill_contiguous` and `fill_solid` are forwarded to the wrapped target, which means that
+/// drawing images and filling rectangles is as fast as drawing in the native color format.
+///
+/// Use [`BinaryColorConverter`] to draw [`BinaryColor`] content with a custom palette.
+///
+/// # Examples
+///
+/// ```
+/// use embedded_graphics::{pixelcolor::Gray8, prelude::*, primitives::Rectangle};
+/// use mipidsi::adapters::ColorConverter;
+///
+/// # let mut display = mipidsi::_mock::new_mock_display();
+/// let mut gray = ColorConverter::<_, Gray8>::new(&mut display);
+///
+/// gray.fill_solid(&Rectangle::new(Point::zero(), Size::new(10, 10)), Gray8::new(128))
+///     .unwrap();
+/// ```
+pub struct ColorConverter<'a, D, C> {
+    target: &'a mut D,
+    color_type: PhantomData<C>,
+}
+
+impl<'a, D, C> ColorConverter<'a, D, C>
+where
+    D: DrawTarget,
+    C: PixelColor,
+    D::Color: From<C>,
+{
+    /// Creates a new color converting adapter for the given draw target.
+    pub fn new(target: &'a mut D) -> Self {
+        Self {
+            target,
+            color_type: PhantomData,
+        }
+    }
+
+    /// Returns a mutable reference to the wrapped draw target.
+    pub fn target(&mut self) -> &mut D {
+        self.target
+    }
+}
+
+impl<D, C> DrawTarget for ColorConverter<'_, D, C>
+where
+    D: DrawTarget,
+    C: PixelColor,
+    D::Color: From<C>,
+{
+    type Color = C;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.target.draw_iter(
+            pixels
+                .into_iter()
+                .map(|Pixel(point, color)| Pixel(point, color.into())),
+        )
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        self.target
+            .fill_contiguous(area, colors.into_iter().map(D::Color::from))
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.target.fill_solid(area, color.into())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.target.clear(color.into())
+    }
+}
+
+impl<D: Dimensions, C> Dimensions for ColorConverter<'_, D, C> {
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+/// Draw target adapter which draws [`BinaryColor`] content using a two color palette.
+///
+/// `BinaryColor::Off` is drawn using the `off` color and `BinaryColor::On` is drawn using
+/// the `on` color. This makes it possible to draw monochrome fonts, icons and images in any
+/// color without converting them first.
+///
+/// # Examples
+///
+/// ```
+/// use embedded_graphics::{
+///     pixelcolor::{BinaryColor, Rgb565},
+///     prelude::*,
+///     primitives::Rectangle,
+/// };
+/// use mipidsi::adapters::BinaryColorConverter;
+///
+/// # let mut display = mipidsi::_mock::new_mock_display();
+/// let mut mono = BinaryColorConverter::new(&mut display, Rgb565::BLACK, Rgb565::CSS_ORANGE);
+///
+/// mono.fill_solid(&Rectangle::new(Point::zero(), Size::new(10, 10)), BinaryColor::On)
+///     .unwrap();
+/// ```
+pub struct BinaryColorConverter<'a, D: DrawTarget> {
+    target: &'a mut D,
+    off: D::Color,
+    on: D::Color,
+}
+
+impl<'a, D: DrawTarget> BinaryColorConverter<'a, D> {
+    /// Creates a new adapter with the given palette.
+    pub fn new(target: &'a mut D, off: D::Color, on: D::Color) -> Self {
+        Self { target, off, on }
+    }
+
+    /// Sets the palette.
+    pub fn set_palette(&mut self, off: D::Color, on: D::Color) {
+        self.off = off;
+        self.on = on;
+    }
+
+    /// Returns a mutable reference to the wrapped draw target.
+    pub fn target(&mut self) -> &mut D {
+        self.target
+    }
+
+    fn convert(&self, color: BinaryColor) -> D::Color {
+        map_binary_color(color, self.off, self.on)
+    }
+}
+
+impl<D: DrawTarget> DrawTarget for BinaryColorConverter<'_, D> {
+    type Color = BinaryColor;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let (off, on) = (self.off, self.on);
+        self.target.draw_iter(
+            pixels
+                .into_iter()
+                .map(|Pixel(point, color)| Pixel(point, map_binary_color(color, off, on))),
+        )
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let (off, on) = (self.off, self.on);
+        self.target.fill_contiguous(
+            area,
+            colors
+                .into_iter()
+                .map(|color| map_binary_color(color, off, on)),
+        )
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let color = self.convert(color);
+        self.target.fill_solid(area, color)
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        let color = self.convert(color);
+        self.target.clear(color)
+    }
+}
+
+impl<D: DrawTarget> Dimensions for BinaryColorConverter<'_, D> {
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+fn map_binary_color<C>(color: BinaryColor, off: C, on: C) -> C {
+    if color.is_on() {
+        on
+    } else {
+        off
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics::{
+        mock_display::MockDisplay,
+        pixelcolor::{BinaryColor, Gray8, Rgb565},
+        prelude::*,
+        primitives::Rectangle,
+    };
+
+    use super::*;
+
+    #[test]
+    fn gray8_is_converted() {
+        let mut display = MockDisplay::<Rgb565>::new();
+        let mut gray = ColorConverter::<_, Gray8>::new(&mut display);
+
+        gray.fill_solid(
+            &Rectangle::new(Point::zero(), Size::new(2, 1)),
+            Gray8::WHITE,
+        )
+        .unwrap();
+
+        display.assert_pattern(&["WW"]);
+    }
+
+    #[test]
+    fn binary_color_uses_palette() {
+        let mut display = MockDisplay::<Rgb565>::new();
+        let mut mono = BinaryColorConverter::new(&mut display, Rgb565::BLUE, Rgb565::RED);
+
+        let colors = [BinaryColor::On, BinaryColor::Off, BinaryColor::On];
+        mono.fill_contiguous(&Rectangle::new(Point::zero(), Size::new(3, 1)), colors)
+            .unwrap();
+
+        display.assert_pattern(&["RBR"]);
+    }
+}
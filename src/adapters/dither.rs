@@ -0,0 +1,361 @@
+use core::marker::PhantomData;
+
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{Dimensions, Point},
+    pixelcolor::{Rgb888, RgbColor},
+    primitives::Rectangle,
+    Pixel,
+};
+
+/// Dithering algorithm used by [`DitheringDrawTarget`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dithering {
+    /// No dithering, colors are rounded to the nearest target color.
+    None,
+    /// Ordered dithering using a 4x4 Bayer matrix.
+    Bayer,
+    /// Floyd–Steinberg error diffusion.
+    ///
+    /// Error diffusion requires the pixels to be drawn in raster order and is
+    /// therefore only used by `fill_contiguous` and `fill_solid` for areas
+    /// which fit into the line buffer. All other drawing operations fall back
+    /// to [`Dithering::Bayer`].
+    FloydSteinberg,
+}
+
+/// 4x4 Bayer threshold matrix.
+const BAYER_4X4: [[i16; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// Draw target adapter which dithers [`Rgb888`] colors to the color format of the wrapped target.
+///
+/// Drawing `Rgb888` assets on a display with a lower color depth (e.g. `Rgb565`) leads to visible
+/// banding in gradients. This adapter accepts `Rgb888` colors and applies the selected
+/// [`Dithering`] while the colors are converted, without buffering the drawn image.
+///
+/// `W` is the width of the line buffer used for [`Dithering::FloydSteinberg`] in pixels. The
+/// buffer uses `12 * W` bytes of RAM and should be at least as wide as the widest area that is
+/// drawn in a single operation. It can be set to `0` if error diffusion isn't used.
+///
+/// # Examples
+///
+/// ```
+/// use embedded_graphics::{pixelcolor::Rgb888, prelude::*, primitives::Rectangle};
+/// use mipidsi::adapters::{Dithering, DitheringDrawTarget};
+///
+/// # let mut display = mipidsi::_mock::new_mock_display();
+/// let mut dithered = DitheringDrawTarget::<_, 240>::new(&mut display, Dithering::FloydSteinberg);
+///
+/// let gradient = (0..240 * 16).map(|i| Rgb888::new((i % 240) as u8, 0, 0));
+/// dithered
+///     .fill_contiguous(&Rectangle::new(Point::zero(), Size::new(240, 16)), gradient)
+///     .unwrap();
+/// ```
+pub struct DitheringDrawTarget<'a, D, const W: usize> {
+    target: &'a mut D,
+    dithering: Dithering,
+    // Accumulated errors for the current and the next row
+    errors: [[[i16; 3]; W]; 2],
+}
+
+impl<'a, D, const W: usize> DitheringDrawTarget<'a, D, W>
+where
+    D: DrawTarget,
+    D::Color: RgbColor + From<Rgb888>,
+    Rgb888: From<D::Color>,
+{
+    /// Creates a new dithering adapter for the given draw target.
+    pub fn new(target: &'a mut D, dithering: Dithering) -> Self {
+        Self {
+            target,
+            dithering,
+            errors: [[[0; 3]; W]; 2],
+        }
+    }
+
+    /// Returns the current [`Dithering`] algorithm.
+    pub fn dithering(&self) -> Dithering {
+        self.dithering
+    }
+
+    /// Sets the [`Dithering`] algorithm.
+    pub fn set_dithering(&mut self, dithering: Dithering) {
+        self.dithering = dithering;
+    }
+
+    /// Returns a mutable reference to the wrapped draw target.
+    pub fn target(&mut self) -> &mut D {
+        self.target
+    }
+}
+
+impl<D, const W: usize> DrawTarget for DitheringDrawTarget<'_, D, W>
+where
+    D: DrawTarget,
+    D::Color: RgbColor + From<Rgb888>,
+    Rgb888: From<D::Color>,
+{
+    type Color = Rgb888;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        if self.dithering == Dithering::None {
+            return self.target.draw_iter(
+                pixels
+                    .into_iter()
+                    .map(|Pixel(point, color)| Pixel(point, D::Color::from(color))),
+            );
+        }
+
+        self.target.draw_iter(
+            pixels
+                .into_iter()
+                .map(|Pixel(point, color)| Pixel(point, ordered(color, point))),
+        )
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let width = area.size.width as usize;
+        let colors = colors.into_iter();
+
+        match self.dithering {
+            Dithering::None => self
+                .target
+                .fill_contiguous(area, colors.map(D::Color::from)),
+            Dithering::FloydSteinberg if width > 0 && width <= W => {
+                for row in self.errors.iter_mut() {
+                    row[..width].fill([0; 3]);
+                }
+                let diffused = ErrorDiffusion {
+                    colors,
+                    color_type: PhantomData,
+                    errors: &mut self.errors,
+                    width,
+                    x: 0,
+                    current: 0,
+                };
+                self.target.fill_contiguous(area, diffused)
+            }
+            _ => {
+                let mut points = RasterPoints::new(area);
+                self.target.fill_contiguous(
+                    area,
+                    colors.map(move |color| ordered(color, points.next_point())),
+                )
+            }
+        }
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let target_color = D::Color::from(color);
+
+        // Dithering is a no-op for colors which can be represented exactly
+        if self.dithering == Dithering::None || Rgb888::from(target_color) == color {
+            return self.target.fill_solid(area, target_color);
+        }
+
+        self.fill_contiguous(area, core::iter::repeat(color))
+    }
+}
+
+impl<D: Dimensions, const W: usize> Dimensions for DitheringDrawTarget<'_, D, W> {
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+/// Returns the Bayer threshold offset for a channel with the given maximum value.
+fn bayer_offset(max: u8, point: Point) -> i16 {
+    let threshold = BAYER_4X4[(point.y & 3) as usize][(point.x & 3) as usize];
+
+    // Map the threshold to a range of +/- half a quantization step
+    (2 * threshold - 15) * 255 / (32 * i16::from(max))
+}
+
+/// Rounds the given channel values to the nearest target color and returns the quantization error.
+fn quantize<C>(value: [i16; 3]) -> (C, [i16; 3])
+where
+    C: Copy + From<Rgb888>,
+    Rgb888: From<C>,
+{
+    let [r, g, b] = value.map(|v| v.clamp(0, 255));
+    let color = C::from(Rgb888::new(r as u8, g as u8, b as u8));
+
+    let quantized = Rgb888::from(color);
+    let error = [
+        r - i16::from(quantized.r()),
+        g - i16::from(quantized.g()),
+        b - i16::from(quantized.b()),
+    ];
+
+    (color, error)
+}
+
+/// Applies ordered dithering to a single pixel.
+fn ordered<C>(color: Rgb888, point: Point) -> C
+where
+    C: RgbColor + From<Rgb888>,
+    Rgb888: From<C>,
+{
+    let value = [
+        i16::from(color.r()) + bayer_offset(C::MAX_R, point),
+        i16::from(color.g()) + bayer_offset(C::MAX_G, point),
+        i16::from(color.b()) + bayer_offset(C::MAX_B, point),
+    ];
+
+    quantize(value).0
+}
+
+/// Iterator over the points of a rectangle in raster order.
+struct RasterPoints {
+    area: Rectangle,
+    offset: Point,
+}
+
+impl RasterPoints {
+    fn new(area: &Rectangle) -> Self {
+        Self {
+            area: *area,
+            offset: Point::zero(),
+        }
+    }
+
+    fn next_point(&mut self) -> Point {
+        let point = self.area.top_left + self.offset;
+
+        self.offset.x += 1;
+        if self.offset.x >= self.area.size.width as i32 {
+            self.offset.x = 0;
+            self.offset.y += 1;
+        }
+
+        point
+    }
+}
+
+/// Iterator which applies Floyd–Steinberg error diffusion to colors in raster order.
+struct ErrorDiffusion<'e, I, C, const W: usize> {
+    colors: I,
+    color_type: PhantomData<C>,
+    errors: &'e mut [[[i16; 3]; W]; 2],
+    width: usize,
+    x: usize,
+    current: usize,
+}
+
+impl<I, C, const W: usize> Iterator for ErrorDiffusion<'_, I, C, W>
+where
+    I: Iterator<Item = Rgb888>,
+    C: Copy + From<Rgb888>,
+    Rgb888: From<C>,
+{
+    type Item = C;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let color = self.colors.next()?;
+
+        let x = self.x;
+        let next = 1 - self.current;
+        let accumulated = self.errors[self.current][x];
+        self.errors[self.current][x] = [0; 3];
+
+        let value = [
+            i16::from(color.r()) + accumulated[0],
+            i16::from(color.g()) + accumulated[1],
+            i16::from(color.b()) + accumulated[2],
+        ];
+        let (quantized, error) = quantize::<C>(value);
+
+        for (channel, error) in error.into_iter().enumerate() {
+            if x + 1 < self.width {
+                self.errors[self.current][x + 1][channel] += error * 7 / 16;
+                self.errors[next][x + 1][channel] += error / 16;
+            }
+            if x > 0 {
+                self.errors[next][x - 1][channel] += error * 3 / 16;
+            }
+            self.errors[next][x][channel] += error * 5 / 16;
+        }
+
+        self.x += 1;
+        if self.x == self.width {
+            self.x = 0;
+            self.current = next;
+        }
+
+        Some(quantized)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics::{
+        mock_display::MockDisplay,
+        pixelcolor::{Rgb565, Rgb888},
+        prelude::*,
+        primitives::Rectangle,
+    };
+
+    use super::*;
+
+    #[test]
+    fn exact_colors_are_not_dithered() {
+        let mut display = MockDisplay::<Rgb565>::new();
+        let mut dithered = DitheringDrawTarget::<_, 8>::new(&mut display, Dithering::Bayer);
+
+        let area = Rectangle::new(Point::zero(), Size::new(4, 4));
+        dithered.fill_solid(&area, Rgb888::WHITE).unwrap();
+
+        display.assert_pattern(&["WWWW", "WWWW", "WWWW", "WWWW"]);
+    }
+
+    #[test]
+    fn bayer_is_noop_for_rgb888_targets() {
+        let mut display = MockDisplay::<Rgb888>::new();
+        let mut dithered = DitheringDrawTarget::<_, 0>::new(&mut display, Dithering::Bayer);
+
+        let color = Rgb888::new(17, 130, 201);
+        let area = Rectangle::new(Point::zero(), Size::new(4, 4));
+        dithered.fill_solid(&area, color).unwrap();
+
+        assert!(area.points().all(|p| display.get_pixel(p) == Some(color)));
+    }
+
+    #[test]
+    fn floyd_steinberg_preserves_average_intensity() {
+        let mut display = MockDisplay::<Rgb565>::new();
+        let mut dithered =
+            DitheringDrawTarget::<_, 16>::new(&mut display, Dithering::FloydSteinberg);
+
+        // Red value between two Rgb565 levels
+        let color = Rgb888::new(12, 0, 0);
+        let area = Rectangle::new(Point::zero(), Size::new(16, 16));
+        dithered.fill_solid(&area, color).unwrap();
+
+        let sum: u32 = area
+            .points()
+            .map(|p| u32::from(Rgb888::from(display.get_pixel(p).unwrap()).r()))
+            .sum();
+        let average = sum / area.size.width / area.size.height;
+
+        assert!(average.abs_diff(12) <= 1, "average = {average}");
+    }
+
+    #[test]
+    fn floyd_steinberg_falls_back_for_wide_areas() {
+        let mut display = MockDisplay::<Rgb565>::new();
+        let mut dithered =
+            DitheringDrawTarget::<_, 4>::new(&mut display, Dithering::FloydSteinberg);
+
+        let area = Rectangle::new(Point::zero(), Size::new(8, 8));
+        dithered.fill_solid(&area, Rgb888::new(12, 0, 0)).unwrap();
+
+        assert!(area.points().all(|p| display.get_pixel(p).is_some()));
+    }
+}
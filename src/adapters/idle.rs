@@ -0,0 +1,138 @@
+use embedded_graphics_core::{
+    draw_target::DrawTarget, geometry::Dimensions, pixelcolor::RgbColor, primitives::Rectangle,
+    Pixel,
+};
+
+/// Draw target adapter which quantizes colors to the 8 idle mode colors.
+///
+/// In idle mode the display controller only uses the most significant bit of
+/// every color channel, which reduces the number of displayed colors to 8.
+/// Drawing content with gradients or anti-aliasing in idle mode can therefore
+/// lead to unexpected results. This adapter rounds every channel to either
+/// off or full intensity before the color is forwarded to the wrapped target,
+/// which makes the result predictable and independent of the display
+/// controller.
+///
+/// Use [`Display::set_idle_mode`](crate::Display::set_idle_mode) to enter and
+/// exit idle mode. The colors can also be sent to the display using 3 bits per
+/// pixel with [`Display::set_pixels_3bpp`](crate::Display::set_pixels_3bpp).
+///
+/// # Examples
+///
+/// ```
+/// use embedded_graphics::{pixelcolor::Rgb565, prelude::*, primitives::Rectangle};
+/// use mipidsi::adapters::IdleColorQuantizer;
+///
+/// # let mut display = mipidsi::_mock::new_mock_display();
+/// display.set_idle_mode(true).unwrap();
+///
+/// let mut idle = IdleColorQuantizer::new(&mut display);
+/// idle.fill_solid(&Rectangle::new(Point::zero(), Size::new(10, 10)), Rgb565::CSS_ORANGE)
+///     .unwrap();
+/// ```
+pub struct IdleColorQuantizer<'a, D> {
+    target: &'a mut D,
+}
+
+impl<'a, D> IdleColorQuantizer<'a, D>
+where
+    D: DrawTarget,
+    D::Color: RgbColor,
+{
+    /// Creates a new idle color quantizer for the given draw target.
+    pub fn new(target: &'a mut D) -> Self {
+        Self { target }
+    }
+
+    /// Returns a mutable reference to the wrapped draw target.
+    pub fn target(&mut self) -> &mut D {
+        self.target
+    }
+}
+
+impl<D> DrawTarget for IdleColorQuantizer<'_, D>
+where
+    D: DrawTarget,
+    D::Color: RgbColor,
+{
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.target.draw_iter(
+            pixels
+                .into_iter()
+                .map(|Pixel(point, color)| Pixel(point, quantize_idle_color(color))),
+        )
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        self.target
+            .fill_contiguous(area, colors.into_iter().map(quantize_idle_color))
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.target.fill_solid(area, quantize_idle_color(color))
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.target.clear(quantize_idle_color(color))
+    }
+}
+
+impl<D: Dimensions> Dimensions for IdleColorQuantizer<'_, D> {
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+/// Returns the 3 bit RGB value of the idle mode color closest to `color`.
+///
+/// Red is stored in bit 2, green in bit 1 and blue in bit 0.
+pub(crate) fn idle_color_bits<C: RgbColor>(color: C) -> u8 {
+    let on = |value: u8, max: u8| u8::from(u16::from(value) * 2 > u16::from(max));
+
+    on(color.r(), C::MAX_R) << 2 | on(color.g(), C::MAX_G) << 1 | on(color.b(), C::MAX_B)
+}
+
+/// Quantizes `color` to the closest idle mode color.
+fn quantize_idle_color<C: RgbColor>(color: C) -> C {
+    match idle_color_bits(color) {
+        0b000 => C::BLACK,
+        0b001 => C::BLUE,
+        0b010 => C::GREEN,
+        0b011 => C::CYAN,
+        0b100 => C::RED,
+        0b101 => C::MAGENTA,
+        0b110 => C::YELLOW,
+        _ => C::WHITE,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::Rgb565, prelude::*};
+
+    use super::*;
+
+    #[test]
+    fn colors_are_quantized() {
+        assert_eq!(quantize_idle_color(Rgb565::new(16, 31, 15)), Rgb565::RED);
+        assert_eq!(quantize_idle_color(Rgb565::new(15, 32, 16)), Rgb565::CYAN);
+        assert_eq!(idle_color_bits(Rgb565::CSS_ORANGE), 0b110);
+
+        let mut display = MockDisplay::<Rgb565>::new();
+        let mut idle = IdleColorQuantizer::new(&mut display);
+        let colors = [Rgb565::CSS_DARK_BLUE, Rgb565::CSS_LIGHT_GRAY];
+        idle.fill_contiguous(&Rectangle::new(Point::zero(), Size::new(2, 1)), colors)
+            .unwrap();
+
+        display.assert_pattern(&["BW"]);
+    }
+}
@@ -0,0 +1,322 @@
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{Dimensions, OriginDimensions, Point, Size},
+    pixelcolor::RgbColor,
+    primitives::Rectangle,
+    Pixel,
+};
+use embedded_hal::digital::OutputPin;
+
+use crate::{
+    backlight::Backlight,
+    interface::{Interface, InterfacePixelFormat},
+    models::Model,
+    Display,
+};
+
+/// Max number of logical pixels which are sent in a single address window.
+const CHUNK_SIZE: usize = 32;
+
+/// Draw target adapter which scales all drawing operations by an integer factor.
+///
+/// The adapter exposes a logical resolution which is `N` times smaller than the
+/// resolution of the wrapped [`Display`] and draws every logical pixel as an
+/// `N`x`N` block of physical pixels. The scaled pixels are generated while the
+/// data is sent to the display, which means that no upscaled copy of the content
+/// is stored in RAM.
+///
+/// # Examples
+///
+/// ```
+/// use embedded_graphics::{pixelcolor::Rgb565, prelude::*, primitives::Rectangle};
+/// use mipidsi::adapters::ScaledDisplay;
+///
+/// # let mut display = mipidsi::_mock::new_mock_display();
+/// let mut scaled = ScaledDisplay::<_, 4>::new(&mut display);
+///
+/// // 240x320 display with 4x scaling
+/// assert_eq!(scaled.size(), Size::new(60, 80));
+///
+/// scaled
+///     .fill_solid(&Rectangle::new(Point::new(1, 1), Size::new(8, 8)), Rgb565::RED)
+///     .unwrap();
+/// ```
+pub struct ScaledDisplay<'a, D, const N: u32> {
+    display: &'a mut D,
+}
+
+impl<'a, DI, M, RST, BL, PWR, const N: u32> ScaledDisplay<'a, Display<DI, M, RST, BL, PWR>, N>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+    PWR: OutputPin,
+{
+    /// Creates a new scaling adapter for the given display.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the scale factor `N` is 0.
+    pub fn new(display: &'a mut Display<DI, M, RST, BL, PWR>) -> Self {
+        assert!(N > 0);
+
+        Self { display }
+    }
+
+    /// Returns a mutable reference to the wrapped display.
+    pub fn display(&mut self) -> &mut Display<DI, M, RST, BL, PWR> {
+        self.display
+    }
+
+    /// Converts a logical area into the corresponding physical area.
+    fn scale(area: &Rectangle) -> Rectangle {
+        Rectangle::new(area.top_left * N as i32, area.size * N)
+    }
+
+    /// Draws a chunk of logical pixels from a single row.
+    fn draw_chunk(&mut self, start: Point, chunk: &[M::ColorFormat]) -> Result<(), DI::Error> {
+        if chunk.is_empty() {
+            return Ok(());
+        }
+
+        let n = N as u16;
+        let sx = start.x as u16 * n;
+        let sy = start.y as u16 * n;
+        let ex = sx + chunk.len() as u16 * n - 1;
+        let ey = sy + n - 1;
+
+        // Every row of the window repeats the chunk with each pixel repeated N times
+        let colors = (0..N).flat_map(|_| {
+            chunk
+                .iter()
+                .flat_map(|&color| core::iter::repeat(color).take(N as usize))
+        });
+
+        self.display.set_pixels(sx, sy, ex, ey, colors)
+    }
+}
+
+impl<DI, M, RST, BL, PWR, const N: u32> DrawTarget
+    for ScaledDisplay<'_, Display<DI, M, RST, BL, PWR>, N>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+    PWR: OutputPin,
+{
+    type Color = M::ColorFormat;
+    type Error = DI::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let bounding_box = self.bounding_box();
+
+        for Pixel(point, color) in pixels {
+            if bounding_box.contains(point) {
+                let block = Self::scale(&Rectangle::new(point, Size::new(1, 1)));
+                self.display.fill_solid(&block, color)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let intersection = area.intersection(&self.bounding_box());
+        if intersection.is_zero_sized() {
+            return Ok(());
+        }
+
+        let mut colors = colors.into_iter();
+        let mut chunk = [M::ColorFormat::BLACK; CHUNK_SIZE];
+
+        for y in area.rows() {
+            let mut len = 0;
+            let mut start = Point::new(intersection.top_left.x, y);
+
+            for x in area.columns() {
+                let Some(color) = colors.next() else {
+                    return self.draw_chunk(start, &chunk[..len]);
+                };
+
+                if !intersection.contains(Point::new(x, y)) {
+                    continue;
+                }
+
+                chunk[len] = color;
+                len += 1;
+
+                if len == CHUNK_SIZE {
+                    self.draw_chunk(start, &chunk)?;
+                    start.x += CHUNK_SIZE as i32;
+                    len = 0;
+                }
+            }
+
+            self.draw_chunk(start, &chunk[..len])?;
+        }
+
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = area.intersection(&self.bounding_box());
+        self.display.fill_solid(&Self::scale(&area), color)
+    }
+}
+
+impl<DI, M, RST, BL, PWR, const N: u32> OriginDimensions
+    for ScaledDisplay<'_, Display<DI, M, RST, BL, PWR>, N>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+    PWR: OutputPin,
+{
+    fn size(&self) -> Size {
+        self.display.size() / N
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cell::RefCell;
+
+    use embedded_graphics_core::pixelcolor::{
+        raw::{RawData, RawU16},
+        Rgb565,
+    };
+
+    use super::*;
+    use crate::{
+        _mock::{MockDelay, MockInterface, MockInterfaceState, MOCK_FRAMEBUFFER_SIZE},
+        models::ILI9341Rgb565,
+        Builder, NoResetPin,
+    };
+
+    fn new_display(
+        state: &RefCell<MockInterfaceState>,
+    ) -> Display<MockInterface<'_>, ILI9341Rgb565, NoResetPin> {
+        let size = MOCK_FRAMEBUFFER_SIZE as u16;
+        Builder::new(ILI9341Rgb565, MockInterface(state))
+            .display_size(size, size)
+            .init(&mut MockDelay)
+            .unwrap()
+    }
+
+    /// Returns the color of every 4x4 block, if all pixels in the block are equal.
+    fn blocks(state: &RefCell<MockInterfaceState>) -> [[u16; 4]; 4] {
+        let pixels = state.borrow().framebuffer;
+
+        core::array::from_fn(|y| {
+            core::array::from_fn(|x| {
+                let value = pixels[y * 4][x * 4];
+                for row in &pixels[y * 4..y * 4 + 4] {
+                    assert!(row[x * 4..x * 4 + 4].iter().all(|&pixel| pixel == value));
+                }
+                value
+            })
+        })
+    }
+
+    fn raw(color: Rgb565) -> u16 {
+        RawU16::from(color).into_inner()
+    }
+
+    #[test]
+    fn scales_coordinates() {
+        let state = RefCell::new(MockInterfaceState::new());
+        let mut display = new_display(&state);
+        let mut scaled = ScaledDisplay::<_, 4>::new(&mut display);
+        assert_eq!(scaled.size(), Size::new(4, 4));
+
+        scaled
+            .draw_iter([
+                Pixel(Point::new(1, 2), Rgb565::RED),
+                Pixel(Point::new(4, 0), Rgb565::GREEN),
+            ])
+            .unwrap();
+
+        let mut expected = [[0; 4]; 4];
+        expected[2][1] = raw(Rgb565::RED);
+        assert_eq!(blocks(&state), expected);
+    }
+
+    #[test]
+    fn expands_fill_solid() {
+        let state = RefCell::new(MockInterfaceState::new());
+        let mut display = new_display(&state);
+        let mut scaled = ScaledDisplay::<_, 4>::new(&mut display);
+
+        scaled
+            .fill_solid(
+                &Rectangle::new(Point::new(1, 1), Size::new(2, 3)),
+                Rgb565::BLUE,
+            )
+            .unwrap();
+
+        let blue = raw(Rgb565::BLUE);
+        assert_eq!(
+            blocks(&state),
+            [
+                [0, 0, 0, 0],
+                [0, blue, blue, 0],
+                [0, blue, blue, 0],
+                [0, blue, blue, 0]
+            ]
+        );
+    }
+
+    #[test]
+    fn expands_fill_contiguous() {
+        let state = RefCell::new(MockInterfaceState::new());
+        let mut display = new_display(&state);
+        let mut scaled = ScaledDisplay::<_, 4>::new(&mut display);
+
+        let colors = [Rgb565::RED, Rgb565::GREEN, Rgb565::BLUE, Rgb565::WHITE];
+        scaled
+            .fill_contiguous(&Rectangle::new(Point::new(2, 1), Size::new(2, 2)), colors)
+            .unwrap();
+
+        let [r, g, b, w] = colors.map(raw);
+        assert_eq!(
+            blocks(&state),
+            [[0, 0, 0, 0], [0, 0, r, g], [0, 0, b, w], [0, 0, 0, 0]]
+        );
+    }
+
+    #[test]
+    fn clips_at_edges() {
+        let state = RefCell::new(MockInterfaceState::new());
+        let mut display = new_display(&state);
+        let mut scaled = ScaledDisplay::<_, 4>::new(&mut display);
+
+        scaled
+            .fill_solid(
+                &Rectangle::new(Point::new(-1, -1), Size::new(2, 2)),
+                Rgb565::BLUE,
+            )
+            .unwrap();
+
+        let colors = [Rgb565::RED, Rgb565::GREEN, Rgb565::BLUE, Rgb565::WHITE];
+        scaled
+            .fill_contiguous(&Rectangle::new(Point::new(3, 3), Size::new(2, 2)), colors)
+            .unwrap();
+
+        let mut expected = [[0; 4]; 4];
+        expected[0][0] = raw(Rgb565::BLUE);
+        expected[3][3] = raw(Rgb565::RED);
+        assert_eq!(blocks(&state), expected);
+    }
+}
@@ -0,0 +1,331 @@
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{Dimensions, OriginDimensions, Point, Size},
+    primitives::Rectangle,
+    Pixel,
+};
+use embedded_hal::digital::OutputPin;
+
+use crate::{
+    backlight::{Backlight, NoBacklight},
+    interface::{Interface, InterfacePixelFormat},
+    models::Model,
+    Display, NoPowerPin, ScrollError,
+};
+
+/// Draw target adapter for hardware scrolled displays.
+///
+/// After the display was scrolled using the vertical scroll start address
+/// (VSCRSAD), framebuffer rows no longer match the visible rows. This adapter
+/// keeps track of the scroll offset and translates the logical y coordinates
+/// used for drawing into the wrapped framebuffer rows. Drawing operations which
+/// cross the wrap around point are split into two windows.
+///
+/// The fixed areas at the top and bottom of the display aren't affected by
+/// scrolling and are drawn without translation.
+///
+/// Hardware scrolling is always relative to the default display orientation,
+/// which means that the adapter only works correctly if the display uses the
+/// default [`Orientation`](crate::options::Orientation).
+///
+/// # Examples
+///
+/// ```
+/// use embedded_graphics::{pixelcolor::Rgb565, prelude::*, primitives::Rectangle};
+/// use mipidsi::adapters::ScrollingCanvas;
+///
+/// # let mut display = mipidsi::_mock::new_mock_display();
+/// let mut canvas = ScrollingCanvas::new(&mut display, 20, 20).unwrap();
+///
+/// canvas.set_scroll_offset(100).unwrap();
+///
+/// // Drawn at the visible position, even though the display is scrolled
+/// canvas
+///     .fill_solid(&Rectangle::new(Point::new(0, 250), Size::new(240, 20)), Rgb565::RED)
+///     .unwrap();
+/// ```
+pub struct ScrollingCanvas<'a, DI, M, RST, BL = NoBacklight, PWR = NoPowerPin>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+    PWR: OutputPin,
+{
+    display: &'a mut Display<DI, M, RST, BL, PWR>,
+    top_fixed_area: u16,
+    scroll_height: u16,
+    offset: u16,
+}
+
+impl<'a, DI, M, RST, BL, PWR> ScrollingCanvas<'a, DI, M, RST, BL, PWR>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+    PWR: OutputPin,
+{
+    /// Creates a new scrolling canvas.
+    ///
+    /// The vertical scroll region of the display is set to the area between
+    /// the top and bottom fixed areas and the scroll offset is reset to 0.
+    ///
+    /// Returns [`ScrollError::FixedAreaTooLarge`] if the fixed areas are larger
+    /// than the display.
+    pub fn new(
+        display: &'a mut Display<DI, M, RST, BL, PWR>,
+        top_fixed_area: u16,
+        bottom_fixed_area: u16,
+    ) -> Result<Self, ScrollError<DI::Error>> {
+        let height = display.bounding_box().size.height as u16;
+        let scroll_height = top_fixed_area
+            .checked_add(bottom_fixed_area)
+            .and_then(|fixed_area| height.checked_sub(fixed_area))
+            .ok_or(ScrollError::FixedAreaTooLarge)?;
+
+        // The scroll region is defined in framebuffer rows
+        let offset_y = display.options.display_offset.1;
+        let framebuffer_top = offset_y + top_fixed_area;
+        let framebuffer_bottom = M::FRAMEBUFFER_SIZE.1 - framebuffer_top - scroll_height;
+        display
+            .set_vertical_scroll_region(framebuffer_top, framebuffer_bottom)
+            .map_err(ScrollError::Interface)?;
+
+        let mut canvas = Self {
+            display,
+            top_fixed_area,
+            scroll_height,
+            offset: 0,
+        };
+        canvas
+            .set_scroll_offset(0)
+            .map_err(ScrollError::Interface)?;
+
+        Ok(canvas)
+    }
+
+    /// Returns a mutable reference to the wrapped display.
+    pub fn display(&mut self) -> &mut Display<DI, M, RST, BL, PWR> {
+        self.display
+    }
+
+    /// Returns the scroll region in logical coordinates.
+    pub fn scroll_area(&self) -> Rectangle {
+        Rectangle::new(
+            Point::new(0, self.top_fixed_area.into()),
+            Size::new(self.size().width, self.scroll_height.into()),
+        )
+    }
+
+    /// Returns the current scroll offset.
+    pub fn scroll_offset(&self) -> u16 {
+        self.offset
+    }
+
+    /// Sets the scroll offset.
+    ///
+    /// The content of the scroll region is shifted upwards by `offset` rows.
+    /// Offsets larger than the height of the scroll region wrap around.
+    pub fn set_scroll_offset(&mut self, offset: u16) -> Result<(), DI::Error> {
+        self.offset = offset.checked_rem(self.scroll_height).unwrap_or(0);
+
+        let framebuffer_top = self.display.options.display_offset.1 + self.top_fixed_area;
+        self.display
+            .set_vertical_scroll_offset(framebuffer_top + self.offset)
+    }
+
+    /// Scrolls the content of the scroll region by `rows`.
+    ///
+    /// Positive values scroll the content upwards, negative values downwards.
+    pub fn scroll_by(&mut self, rows: i32) -> Result<(), DI::Error> {
+        let height = i32::from(self.scroll_height).max(1);
+        let offset = (i32::from(self.offset) + rows).rem_euclid(height);
+
+        self.set_scroll_offset(offset as u16)
+    }
+
+    /// Returns the row bands with a constant translation.
+    ///
+    /// Every band contains the logical start and end (exclusive) row and the
+    /// offset which needs to be added to get the framebuffer row.
+    fn bands(&self) -> [(i32, i32, i32); 4] {
+        let top = i32::from(self.top_fixed_area);
+        let bottom = top + i32::from(self.scroll_height);
+        let offset = i32::from(self.offset);
+        let wrap = bottom - offset;
+
+        [
+            (i32::MIN, top, 0),
+            (top, wrap, offset),
+            (wrap, bottom, offset - i32::from(self.scroll_height)),
+            (bottom, i32::MAX, 0),
+        ]
+    }
+
+    /// Splits an area into parts with a constant translation.
+    ///
+    /// Returns the logical part of the area and the translated part.
+    fn split(&self, area: &Rectangle) -> impl Iterator<Item = (Rectangle, Rectangle)> {
+        let area = *area;
+
+        self.bands()
+            .into_iter()
+            .filter_map(move |(start, end, offset)| {
+                let top = area.top_left.y.max(start);
+                let bottom = (area.top_left.y + area.size.height as i32).min(end);
+                if top >= bottom {
+                    return None;
+                }
+
+                let part = Rectangle::new(
+                    Point::new(area.top_left.x, top),
+                    Size::new(area.size.width, (bottom - top) as u32),
+                );
+                let translated = Rectangle::new(part.top_left + Point::new(0, offset), part.size);
+                Some((part, translated))
+            })
+    }
+}
+
+impl<DI, M, RST, BL, PWR> DrawTarget for ScrollingCanvas<'_, DI, M, RST, BL, PWR>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+    PWR: OutputPin,
+{
+    type Color = M::ColorFormat;
+    type Error = DI::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let bands = self.bands();
+        let translate = |Pixel(point, color): Pixel<Self::Color>| {
+            Pixel(point + Point::new(0, band_offset(&bands, point.y)), color)
+        };
+
+        self.display.draw_iter(pixels.into_iter().map(translate))
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let mut colors = colors.into_iter();
+
+        for (part, translated) in self.split(area) {
+            let count = part.size.width as usize * part.size.height as usize;
+
+            // Parts which are (partially) outside the display aren't completely consumed
+            let mut part_colors = colors.by_ref().take(count);
+            self.display
+                .fill_contiguous(&translated, &mut part_colors)?;
+            part_colors.for_each(drop);
+        }
+
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        for (_, translated) in self.split(area) {
+            self.display.fill_solid(&translated, color)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<DI, M, RST, BL, PWR> OriginDimensions for ScrollingCanvas<'_, DI, M, RST, BL, PWR>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+    PWR: OutputPin,
+{
+    fn size(&self) -> Size {
+        self.display.size()
+    }
+}
+
+/// Returns the offset of the band which contains row `y`.
+fn band_offset(bands: &[(i32, i32, i32)], y: i32) -> i32 {
+    bands
+        .iter()
+        .find(|&&(start, end, _)| (start..end).contains(&y))
+        .map_or(0, |&(_, _, offset)| offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_mock::new_mock_display;
+
+    #[test]
+    fn translates_scrolled_rows() {
+        let mut display = new_mock_display();
+        let mut canvas = ScrollingCanvas::new(&mut display, 20, 20).unwrap();
+        assert_eq!(canvas.scroll_area().size, Size::new(240, 280));
+
+        canvas.set_scroll_offset(100).unwrap();
+        let bands = canvas.bands();
+        let translate_y = |y| y + band_offset(&bands, y);
+        assert_eq!(translate_y(0), 0);
+        assert_eq!(translate_y(20), 120);
+        assert_eq!(translate_y(199), 299);
+        assert_eq!(translate_y(200), 20);
+        assert_eq!(translate_y(299), 119);
+        assert_eq!(translate_y(300), 300);
+
+        canvas.scroll_by(-150).unwrap();
+        assert_eq!(canvas.scroll_offset(), 230);
+    }
+
+    #[test]
+    fn rejects_large_fixed_areas() {
+        let mut display = new_mock_display();
+
+        assert!(matches!(
+            ScrollingCanvas::new(&mut display, 200, 121),
+            Err(ScrollError::FixedAreaTooLarge)
+        ));
+        assert!(matches!(
+            ScrollingCanvas::new(&mut display, u16::MAX, 1),
+            Err(ScrollError::FixedAreaTooLarge)
+        ));
+    }
+
+    #[test]
+    fn splits_at_wrap_around() {
+        let mut display = new_mock_display();
+        let mut canvas = ScrollingCanvas::new(&mut display, 20, 20).unwrap();
+        canvas.set_scroll_offset(100).unwrap();
+
+        let area = Rectangle::new(Point::new(5, 190), Size::new(10, 20));
+        let mut parts = canvas.split(&area);
+
+        assert_eq!(
+            parts.next(),
+            Some((
+                Rectangle::new(Point::new(5, 190), Size::new(10, 10)),
+                Rectangle::new(Point::new(5, 290), Size::new(10, 10)),
+            ))
+        );
+        assert_eq!(
+            parts.next(),
+            Some((
+                Rectangle::new(Point::new(5, 200), Size::new(10, 10)),
+                Rectangle::new(Point::new(5, 20), Size::new(10, 10)),
+            ))
+        );
+        assert_eq!(parts.next(), None);
+    }
+}
@@ -0,0 +1,519 @@
+//! Delta RLE animations.
+//!
+//! Animations are stored in a compact format which only contains the parts of
+//! each frame which differ from the previous frame. This makes it possible to
+//! play boot animations or status indicators directly from flash, without
+//! decoding frames into RAM.
+//!
+//! Animations can be created with the [`Encoder`], e.g. in a build script or a
+//! small host side tool, and are played using an [`AnimationPlayer`].
+//!
+//! # Format
+//!
+//! All header fields are stored as little endian integers.
+//!
+//! | Size         | Content                                  |
+//! |--------------|------------------------------------------|
+//! | 4            | Magic bytes `DRLE`                       |
+//! | 2            | Width                                    |
+//! | 2            | Height                                   |
+//! | 2            | Number of frames                         |
+//! | 2            | Frame delay in milliseconds              |
+//! | _variable_   | Frames                                   |
+//!
+//! Each frame starts with the length of the frame data (4 bytes), followed by
+//! the number of changed rectangles (2 bytes) and the rectangles. Every
+//! rectangle consists of its position and size (4 x 2 bytes) and RLE encoded
+//! pixel runs, which cover the rectangle row by row.
+//!
+//! A run starts with a single byte. If the highest bit is set, the run repeats
+//! the next pixel `(byte & 0x7F) + 1` times. Otherwise `(byte & 0x7F) + 1`
+//! pixels follow the run byte. Pixels are stored as big endian [`Rgb565`]
+//! values.
+
+use embedded_graphics_core::{
+    geometry::{Dimensions, Point, Size},
+    pixelcolor::{raw::RawU16, Rgb565},
+    primitives::Rectangle,
+};
+use embedded_hal::{delay::DelayNs, digital::OutputPin};
+
+use crate::{
+    backlight::Backlight,
+    dcs::{InterfaceExt, WriteMemoryStart},
+    interface::{Interface, InterfacePixelFormat},
+    models::Model,
+    Display,
+};
+
+mod encoder;
+pub use encoder::*;
+
+const MAGIC: [u8; 4] = *b"DRLE";
+const HEADER_SIZE: usize = 12;
+const RECT_HEADER_SIZE: usize = 8;
+
+/// Flag for runs which repeat a single pixel.
+const RUN_REPEAT: u8 = 0x80;
+/// Max number of pixels in a single run.
+const MAX_RUN_LENGTH: usize = 128;
+
+/// Error returned when parsing invalid animation data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnimationError {
+    /// The header is invalid.
+    InvalidHeader,
+    /// The frame with the given index is invalid or truncated.
+    InvalidFrame(u16),
+}
+
+/// A delta RLE animation.
+///
+/// The animation data is validated when the animation is created and can be
+/// drawn without further error checks.
+#[derive(Debug, Clone, Copy)]
+pub struct Animation<'a> {
+    size: Size,
+    frame_count: u16,
+    frame_delay_ms: u16,
+    frames: &'a [u8],
+}
+
+impl<'a> Animation<'a> {
+    /// Parses and validates an animation.
+    pub fn new(data: &'a [u8]) -> Result<Self, AnimationError> {
+        if data.len() < HEADER_SIZE || data[0..4] != MAGIC {
+            return Err(AnimationError::InvalidHeader);
+        }
+
+        let animation = Self {
+            size: Size::new(u32::from(u16_at(data, 4)), u32::from(u16_at(data, 6))),
+            frame_count: u16_at(data, 8),
+            frame_delay_ms: u16_at(data, 10),
+            frames: &data[HEADER_SIZE..],
+        };
+
+        let mut rest = animation.frames;
+        for index in 0..animation.frame_count {
+            let (frame, tail) = split_frame(rest).ok_or(AnimationError::InvalidFrame(index))?;
+            if !frame.is_valid(animation.size) {
+                return Err(AnimationError::InvalidFrame(index));
+            }
+            rest = tail;
+        }
+
+        if !rest.is_empty() {
+            return Err(AnimationError::InvalidHeader);
+        }
+
+        Ok(animation)
+    }
+
+    /// Returns the size of the animation.
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    /// Returns the number of frames.
+    pub fn frame_count(&self) -> u16 {
+        self.frame_count
+    }
+
+    /// Returns the delay between two frames in milliseconds.
+    pub fn frame_delay_ms(&self) -> u16 {
+        self.frame_delay_ms
+    }
+
+    /// Returns an iterator over the frames.
+    pub fn frames(&self) -> Frames<'a> {
+        Frames {
+            data: self.frames,
+            remaining: self.frame_count,
+        }
+    }
+}
+
+/// Iterator over the frames of an [`Animation`].
+#[derive(Debug, Clone)]
+pub struct Frames<'a> {
+    data: &'a [u8],
+    remaining: u16,
+}
+
+impl<'a> Iterator for Frames<'a> {
+    type Item = Frame<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+
+        // The frames were validated by `Animation::new`
+        let (frame, rest) = split_frame(self.data)?;
+        self.data = rest;
+
+        Some(frame)
+    }
+}
+
+/// A single animation frame.
+///
+/// The frame contains the rectangles which changed since the previous frame.
+#[derive(Debug, Clone, Copy)]
+pub struct Frame<'a> {
+    rect_count: u16,
+    data: &'a [u8],
+}
+
+impl<'a> Frame<'a> {
+    /// Returns the number of changed rectangles.
+    pub fn rect_count(&self) -> u16 {
+        self.rect_count
+    }
+
+    fn rects(&self) -> FrameRects<'a> {
+        FrameRects {
+            data: self.data,
+            remaining: self.rect_count,
+        }
+    }
+
+    fn is_valid(&self, size: Size) -> bool {
+        let bounds = Rectangle::new(Point::zero(), size);
+        let mut rects = self.rects();
+
+        for _ in 0..self.rect_count {
+            let Some(rect) = rects.next() else {
+                return false;
+            };
+
+            if rect.area.is_zero_sized()
+                || !bounds.contains(rect.area.top_left)
+                || !rect.area.bottom_right().is_some_and(|p| bounds.contains(p))
+            {
+                return false;
+            }
+        }
+
+        rects.data.is_empty()
+    }
+}
+
+/// A changed rectangle with its RLE encoded pixels.
+struct FrameRect<'a> {
+    area: Rectangle,
+    runs: &'a [u8],
+}
+
+impl<'a> FrameRect<'a> {
+    fn runs(&self) -> Runs<'a> {
+        Runs { data: self.runs }
+    }
+}
+
+/// Iterator over the rectangles in a frame.
+///
+/// Returns `None` if the rectangle data is truncated or the runs don't match
+/// the rectangle size.
+struct FrameRects<'a> {
+    data: &'a [u8],
+    remaining: u16,
+}
+
+impl<'a> Iterator for FrameRects<'a> {
+    type Item = FrameRect<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 || self.data.len() < RECT_HEADER_SIZE {
+            return None;
+        }
+        self.remaining -= 1;
+
+        let area = Rectangle::new(
+            Point::new(
+                i32::from(u16_at(self.data, 0)),
+                i32::from(u16_at(self.data, 2)),
+            ),
+            Size::new(
+                u32::from(u16_at(self.data, 4)),
+                u32::from(u16_at(self.data, 6)),
+            ),
+        );
+        let data = &self.data[RECT_HEADER_SIZE..];
+
+        // Find the end of the runs which cover this rectangle
+        let mut pixels = area.size.width * area.size.height;
+        let mut runs = Runs { data };
+        while pixels > 0 {
+            let count = runs.next()?.len() as u32;
+            pixels = pixels.checked_sub(count)?;
+        }
+
+        let len = data.len() - runs.data.len();
+        self.data = runs.data;
+
+        Some(FrameRect {
+            area,
+            runs: &data[..len],
+        })
+    }
+}
+
+/// A run of pixels.
+enum Run<'a> {
+    /// Big endian pixel data.
+    Literal(&'a [u8]),
+    /// A single pixel which is repeated.
+    Repeat(Rgb565, u8),
+}
+
+impl Run<'_> {
+    fn len(&self) -> usize {
+        match self {
+            Self::Literal(pixels) => pixels.len() / 2,
+            Self::Repeat(_, count) => usize::from(*count),
+        }
+    }
+}
+
+/// Iterator over RLE runs.
+///
+/// Stops at the end of the data or if the data is truncated.
+struct Runs<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Iterator for Runs<'a> {
+    type Item = Run<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (&header, rest) = self.data.split_first()?;
+        let count = (header & !RUN_REPEAT) as usize + 1;
+
+        let (run, rest) = if header & RUN_REPEAT != 0 {
+            let pixel = rest.get(..2)?;
+            (
+                Run::Repeat(pixel_from_bytes(pixel), count as u8),
+                &rest[2..],
+            )
+        } else {
+            let pixels = rest.get(..count * 2)?;
+            (Run::Literal(pixels), &rest[count * 2..])
+        };
+        self.data = rest;
+
+        Some(run)
+    }
+}
+
+fn u16_at(data: &[u8], index: usize) -> u16 {
+    u16::from_le_bytes([data[index], data[index + 1]])
+}
+
+fn pixel_from_bytes(bytes: &[u8]) -> Rgb565 {
+    RawU16::new(u16::from_be_bytes([bytes[0], bytes[1]])).into()
+}
+
+/// Splits the first frame from the frame data.
+fn split_frame(data: &[u8]) -> Option<(Frame<'_>, &[u8])> {
+    let len = u32::from_le_bytes(data.get(..4)?.try_into().ok()?) as usize;
+    let end = len.checked_add(4)?;
+    let frame = data.get(4..end)?;
+    if frame.len() < 2 {
+        return None;
+    }
+
+    let frame = Frame {
+        rect_count: u16_at(frame, 0),
+        data: &frame[2..],
+    };
+
+    Some((frame, &data[end..]))
+}
+
+/// Plays a delta RLE [`Animation`].
+///
+/// The player keeps track of the current frame and draws the frames in order,
+/// starting from the first frame again after the last frame was drawn. Frames
+/// can either be paced by a delay, using [`play`](Self::play), or by calling
+/// [`draw_next_frame`](Self::draw_next_frame) directly, e.g. after waiting for
+/// the tearing effect signal of the display.
+///
+/// # Examples
+///
+/// ```
+/// use embedded_graphics::{pixelcolor::Rgb565, prelude::*};
+/// use mipidsi::animation::{Animation, AnimationPlayer, Encoder};
+///
+/// # let mut display = mipidsi::_mock::new_mock_display();
+/// # let mut delay = mipidsi::_mock::MockDelay;
+/// let first = [Rgb565::BLACK; 16];
+/// let mut second = first;
+/// second[5] = Rgb565::RED;
+///
+/// let mut buffer = [0; 128];
+/// let mut encoder = Encoder::new(&mut buffer, Size::new(4, 4), 100).unwrap();
+/// encoder.add_frame(None, &first).unwrap();
+/// encoder.add_frame(Some(&first), &second).unwrap();
+/// let data = encoder.finish();
+///
+/// let animation = Animation::new(data).unwrap();
+/// let mut player = AnimationPlayer::new(animation, Point::new(10, 10));
+/// player.play(&mut display, &mut delay).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct AnimationPlayer<'a> {
+    animation: Animation<'a>,
+    position: Point,
+    frames: Frames<'a>,
+}
+
+impl<'a> AnimationPlayer<'a> {
+    /// Creates a new player which draws the animation at `position`.
+    pub fn new(animation: Animation<'a>, position: Point) -> Self {
+        Self {
+            animation,
+            position,
+            frames: animation.frames(),
+        }
+    }
+
+    /// Returns the animation.
+    pub fn animation(&self) -> &Animation<'a> {
+        &self.animation
+    }
+
+    /// Returns the index of the next frame.
+    pub fn next_frame_index(&self) -> u16 {
+        self.animation.frame_count - self.frames.remaining
+    }
+
+    /// Restarts the animation from the first frame.
+    pub fn rewind(&mut self) {
+        self.frames = self.animation.frames();
+    }
+
+    /// Draws the next frame.
+    pub fn draw_next_frame<DI, M, RST, BL, PWR>(
+        &mut self,
+        display: &mut Display<DI, M, RST, BL, PWR>,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+        M: Model,
+        M::ColorFormat: InterfacePixelFormat<DI::Word> + From<Rgb565>,
+        RST: OutputPin,
+        BL: Backlight,
+        PWR: OutputPin,
+    {
+        let frame = match self.frames.next() {
+            Some(frame) => frame,
+            None => {
+                self.rewind();
+                match self.frames.next() {
+                    Some(frame) => frame,
+                    None => return Ok(()),
+                }
+            }
+        };
+
+        display.draw_animation_frame(self.position, &frame)
+    }
+
+    /// Plays the remaining frames of the animation.
+    ///
+    /// The animation's frame delay is inserted after each frame.
+    pub fn play<DI, M, RST, BL, PWR, D>(
+        &mut self,
+        display: &mut Display<DI, M, RST, BL, PWR>,
+        delay: &mut D,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+        M: Model,
+        M::ColorFormat: InterfacePixelFormat<DI::Word> + From<Rgb565>,
+        RST: OutputPin,
+        BL: Backlight,
+        PWR: OutputPin,
+        D: DelayNs,
+    {
+        if self.frames.remaining == 0 {
+            self.rewind();
+        }
+
+        for frame in self.frames.by_ref() {
+            display.draw_animation_frame(self.position, &frame)?;
+            delay.delay_ms(u32::from(self.animation.frame_delay_ms));
+        }
+
+        Ok(())
+    }
+}
+
+impl<DI, M, RST, BL, PWR> Display<DI, M, RST, BL, PWR>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word> + From<Rgb565>,
+    RST: OutputPin,
+    BL: Backlight,
+    PWR: OutputPin,
+{
+    /// Draws a single animation frame.
+    ///
+    /// Each changed rectangle is sent using a single address window. Literal
+    /// pixel runs are sent as pixel data and repeated runs are sent using the
+    /// interface's repeated pixel support.
+    ///
+    /// Rectangles which aren't completely inside the display are skipped. Does
+    /// nothing while the display is powered off, see
+    /// [`power_off`](Self::power_off).
+    pub fn draw_animation_frame(
+        &mut self,
+        position: Point,
+        frame: &Frame,
+    ) -> Result<(), DI::Error> {
+        if !self.state.powered {
+            return Ok(());
+        }
+
+        let bounds = self.bounding_box();
+
+        for rect in frame.rects() {
+            let area = Rectangle::new(rect.area.top_left + position, rect.area.size);
+            let Some(bottom_right) = area.bottom_right() else {
+                continue;
+            };
+            if !bounds.contains(area.top_left) || !bounds.contains(bottom_right) {
+                continue;
+            }
+
+            self.set_address_window(
+                area.top_left.x as u16,
+                area.top_left.y as u16,
+                bottom_right.x as u16,
+                bottom_right.y as u16,
+            )?;
+            self.di.write_command(WriteMemoryStart)?;
+
+            for run in rect.runs() {
+                match run {
+                    Run::Literal(pixels) => M::ColorFormat::send_pixels(
+                        &mut self.di,
+                        pixels
+                            .chunks_exact(2)
+                            .map(|pixel| pixel_from_bytes(pixel).into()),
+                    )?,
+                    Run::Repeat(pixel, count) => M::ColorFormat::send_repeated_pixel(
+                        &mut self.di,
+                        pixel.into(),
+                        u32::from(count),
+                    )?,
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
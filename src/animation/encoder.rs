@@ -0,0 +1,348 @@
+use embedded_graphics_core::{
+    geometry::Size,
+    pixelcolor::{raw::ToBytes, Rgb565},
+};
+
+use super::{MAGIC, MAX_RUN_LENGTH, RUN_REPEAT};
+
+/// Error returned by the [`Encoder`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncodeError {
+    /// The output buffer is too small.
+    BufferTooSmall,
+    /// The animation size is zero or doesn't fit into the format, or a frame
+    /// doesn't match the animation size.
+    InvalidSize,
+    /// The animation already contains the max number of frames.
+    TooManyFrames,
+}
+
+/// Delta RLE animation encoder.
+///
+/// The encoder writes the animation into a caller provided buffer and doesn't
+/// require an allocator. It can be used on the target, but is mostly intended to
+/// be used in build scripts or host side tools to convert animations into the
+/// format used by the [`AnimationPlayer`](super::AnimationPlayer).
+///
+/// Frames are passed as slices of pixels in row major order. Each frame is
+/// compared to the previous frame and only the changed rows are stored.
+pub struct Encoder<'b> {
+    buffer: &'b mut [u8],
+    len: usize,
+    width: usize,
+    height: usize,
+    frame_count: u16,
+}
+
+impl<'b> Encoder<'b> {
+    /// Creates a new encoder and writes the animation header.
+    pub fn new(buffer: &'b mut [u8], size: Size, frame_delay_ms: u16) -> Result<Self, EncodeError> {
+        let width = u16::try_from(size.width).map_err(|_| EncodeError::InvalidSize)?;
+        let height = u16::try_from(size.height).map_err(|_| EncodeError::InvalidSize)?;
+        if width == 0 || height == 0 {
+            return Err(EncodeError::InvalidSize);
+        }
+
+        let mut encoder = Self {
+            buffer,
+            len: 0,
+            width: usize::from(width),
+            height: usize::from(height),
+            frame_count: 0,
+        };
+
+        encoder.push(&MAGIC)?;
+        encoder.push(&width.to_le_bytes())?;
+        encoder.push(&height.to_le_bytes())?;
+        encoder.push(&0u16.to_le_bytes())?;
+        encoder.push(&frame_delay_ms.to_le_bytes())?;
+
+        Ok(encoder)
+    }
+
+    /// Adds a frame to the animation.
+    ///
+    /// `previous` must contain the previously added frame, or `None` for the
+    /// first frame, which is always stored completely. If the buffer is too
+    /// small the frame isn't added and the encoder can still be used.
+    pub fn add_frame(
+        &mut self,
+        previous: Option<&[Rgb565]>,
+        frame: &[Rgb565],
+    ) -> Result<(), EncodeError> {
+        let pixel_count = self.width * self.height;
+        if frame.len() != pixel_count || previous.is_some_and(|p| p.len() != pixel_count) {
+            return Err(EncodeError::InvalidSize);
+        }
+        let frame_count = self
+            .frame_count
+            .checked_add(1)
+            .ok_or(EncodeError::TooManyFrames)?;
+
+        let start = self.len;
+        let result = self.encode_frame(previous, frame);
+        if result.is_err() {
+            self.len = start;
+            return result;
+        }
+
+        self.frame_count = frame_count;
+        self.buffer[8..10].copy_from_slice(&frame_count.to_le_bytes());
+
+        Ok(())
+    }
+
+    /// Finishes the animation and returns the encoded data.
+    pub fn finish(self) -> &'b [u8] {
+        &self.buffer[..self.len]
+    }
+
+    fn encode_frame(
+        &mut self,
+        previous: Option<&[Rgb565]>,
+        frame: &[Rgb565],
+    ) -> Result<(), EncodeError> {
+        let frame_start = self.len;
+        // Frame length and rectangle count are filled in at the end
+        self.push(&[0; 6])?;
+
+        let (width, height) = (self.width, self.height);
+        let changed_columns = |y: usize| {
+            let row = y * width..(y + 1) * width;
+            match previous {
+                Some(previous) => {
+                    let changed = |x: &usize| frame[row.start + x] != previous[row.start + x];
+                    let first = (0..width).find(changed)?;
+                    let last = (0..width).rev().find(changed)?;
+                    Some((first, last))
+                }
+                None => Some((0, width - 1)),
+            }
+        };
+
+        // Consecutive rows with changes are combined into a single rectangle
+        let mut rect_count = 0u16;
+        let mut y = 0;
+        while y < height {
+            let Some((mut first, mut last)) = changed_columns(y) else {
+                y += 1;
+                continue;
+            };
+
+            let start_y = y;
+            y += 1;
+            while let Some((f, l)) = (y < height).then(|| changed_columns(y)).flatten() {
+                first = first.min(f);
+                last = last.max(l);
+                y += 1;
+            }
+
+            self.encode_rect(frame, first, start_y, last - first + 1, y - start_y)?;
+            rect_count += 1;
+        }
+
+        let frame_len = (self.len - frame_start - 4) as u32;
+        self.buffer[frame_start..frame_start + 4].copy_from_slice(&frame_len.to_le_bytes());
+        self.buffer[frame_start + 4..frame_start + 6].copy_from_slice(&rect_count.to_le_bytes());
+
+        Ok(())
+    }
+
+    fn encode_rect(
+        &mut self,
+        frame: &[Rgb565],
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+    ) -> Result<(), EncodeError> {
+        for value in [x, y, width, height] {
+            self.push(&(value as u16).to_le_bytes())?;
+        }
+
+        let stride = self.width;
+        let pixel = |i: usize| frame[(y + i / width) * stride + x + i % width];
+        let count = width * height;
+
+        let mut literal_start = 0;
+        let mut i = 0;
+        while i < count {
+            let color = pixel(i);
+            let mut run = 1;
+            while i + run < count && run < MAX_RUN_LENGTH && pixel(i + run) == color {
+                run += 1;
+            }
+
+            if run >= 2 {
+                self.push_literal(literal_start..i, pixel)?;
+                self.push(&[RUN_REPEAT | (run - 1) as u8])?;
+                self.push(&color.to_be_bytes())?;
+                i += run;
+                literal_start = i;
+            } else {
+                i += 1;
+                if i - literal_start == MAX_RUN_LENGTH {
+                    self.push_literal(literal_start..i, pixel)?;
+                    literal_start = i;
+                }
+            }
+        }
+
+        self.push_literal(literal_start..count, pixel)
+    }
+
+    fn push_literal(
+        &mut self,
+        range: core::ops::Range<usize>,
+        pixel: impl Fn(usize) -> Rgb565,
+    ) -> Result<(), EncodeError> {
+        if range.is_empty() {
+            return Ok(());
+        }
+
+        self.push(&[(range.len() - 1) as u8])?;
+        for i in range {
+            self.push(&pixel(i).to_be_bytes())?;
+        }
+
+        Ok(())
+    }
+
+    fn push(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        let end = self.len + bytes.len();
+        self.buffer
+            .get_mut(self.len..end)
+            .ok_or(EncodeError::BufferTooSmall)?
+            .copy_from_slice(bytes);
+        self.len = end;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics_core::pixelcolor::RgbColor;
+
+    use super::*;
+    use crate::animation::{pixel_from_bytes, Animation, AnimationError, Frame, Run};
+
+    const SIZE: Size = Size::new(8, 4);
+
+    fn apply(framebuffer: &mut [Rgb565], frame: &Frame) {
+        for rect in frame.rects() {
+            let area = rect.area;
+            let mut pixels = area
+                .rows()
+                .flat_map(|y| area.columns().map(move |x| (x, y)));
+            for run in rect.runs() {
+                let mut set = |color| {
+                    let (x, y) = pixels.next().unwrap();
+                    framebuffer[y as usize * SIZE.width as usize + x as usize] = color;
+                };
+                match run {
+                    Run::Literal(bytes) => {
+                        bytes.chunks_exact(2).for_each(|b| set(pixel_from_bytes(b)))
+                    }
+                    Run::Repeat(color, count) => (0..count).for_each(|_| set(color)),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn encodes_changed_rows() {
+        let first = [Rgb565::BLUE; 32];
+        let mut second = first;
+        second[8 + 2] = Rgb565::RED;
+        second[16 + 5] = Rgb565::GREEN;
+        let mut third = second;
+        for pixel in &mut third[24..] {
+            *pixel = Rgb565::WHITE;
+        }
+
+        let mut buffer = [0; 256];
+        let mut encoder = Encoder::new(&mut buffer, SIZE, 50).unwrap();
+        encoder.add_frame(None, &first).unwrap();
+        encoder.add_frame(Some(&first), &second).unwrap();
+        encoder.add_frame(Some(&second), &third).unwrap();
+        encoder.add_frame(Some(&third), &third).unwrap();
+        let data = encoder.finish();
+
+        let animation = Animation::new(data).unwrap();
+        assert_eq!(animation.size(), SIZE);
+        assert_eq!(animation.frame_count(), 4);
+        assert_eq!(animation.frame_delay_ms(), 50);
+
+        let mut framebuffer = [Rgb565::BLACK; 32];
+        let mut frames = animation.frames();
+        for (expected, rect_count) in [(&first, 1), (&second, 1), (&third, 1), (&third, 0)] {
+            let frame = frames.next().unwrap();
+            assert_eq!(frame.rect_count(), rect_count);
+            apply(&mut framebuffer, &frame);
+            assert_eq!(&framebuffer, expected);
+        }
+        assert!(frames.next().is_none());
+    }
+
+    #[test]
+    fn buffer_too_small_keeps_previous_frames() {
+        let first = [Rgb565::BLUE; 32];
+        let mut second = first;
+        for (i, pixel) in second.iter_mut().enumerate() {
+            *pixel = Rgb565::new(i as u8, 0, 0);
+        }
+
+        let mut buffer = [0; 40];
+        let mut encoder = Encoder::new(&mut buffer, SIZE, 0).unwrap();
+        encoder.add_frame(None, &first).unwrap();
+        assert_eq!(
+            encoder.add_frame(Some(&first), &second),
+            Err(EncodeError::BufferTooSmall)
+        );
+        assert_eq!(
+            encoder.add_frame(Some(&first), &second[..8]),
+            Err(EncodeError::InvalidSize)
+        );
+
+        let animation = Animation::new(encoder.finish()).unwrap();
+        assert_eq!(animation.frame_count(), 1);
+    }
+
+    #[test]
+    fn rejects_zero_size() {
+        let mut buffer = [0; 64];
+        for size in [Size::new(0, 4), Size::new(8, 0)] {
+            assert_eq!(
+                Encoder::new(&mut buffer, size, 0).err(),
+                Some(EncodeError::InvalidSize)
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_truncated_animations() {
+        let first = [Rgb565::BLUE; 32];
+        let mut buffer = [0; 64];
+        let mut encoder = Encoder::new(&mut buffer, SIZE, 0).unwrap();
+        encoder.add_frame(None, &first).unwrap();
+        let data = encoder.finish();
+
+        assert_eq!(
+            Animation::new(&data[..data.len() - 1]).err(),
+            Some(AnimationError::InvalidFrame(0))
+        );
+        assert_eq!(
+            Animation::new(&data[..4]).err(),
+            Some(AnimationError::InvalidHeader)
+        );
+
+        // Frame lengths which overflow are rejected
+        let mut data: [u8; 64] = buffer;
+        data[12..16].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert_eq!(
+            Animation::new(&data).err(),
+            Some(AnimationError::InvalidFrame(0))
+        );
+    }
+}
@@ -0,0 +1,262 @@
+//! Backlight control.
+//!
+//! The backlight can be passed to the [`Builder`](crate::Builder) by using the
+//! [`backlight`](crate::Builder::backlight) method. Backlights which are
+//! switched using an [`OutputPin`] are supported by [`PinBacklight`] and
+//! backlights which are dimmed using PWM by [`PwmBacklight`].
+
+use core::convert::Infallible;
+
+use embedded_hal::{delay::DelayNs, digital::OutputPin, pwm::SetDutyCycle};
+
+use crate::{
+    interface::{Interface, InterfacePixelFormat},
+    models::Model,
+    Display,
+};
+
+/// Minimum interval between the steps of a backlight fade.
+const FADE_STEP_MS: u32 = 10;
+
+/// Display backlight.
+pub trait Backlight {
+    /// Error type.
+    type Error;
+
+    /// Sets the backlight level.
+    ///
+    /// `0` turns the backlight off and `255` sets it to the max brightness.
+    fn set_level(&mut self, level: u8) -> Result<(), Self::Error>;
+}
+
+/// Marker type for no backlight.
+pub enum NoBacklight {}
+
+impl Backlight for NoBacklight {
+    type Error = Infallible;
+
+    fn set_level(&mut self, _level: u8) -> Result<(), Self::Error> {
+        match *self {}
+    }
+}
+
+/// Backlight which is switched on and off using an [`OutputPin`].
+///
+/// All levels except `0` turn the backlight on.
+pub struct PinBacklight<P> {
+    pin: P,
+    active_low: bool,
+}
+
+impl<P: OutputPin> PinBacklight<P> {
+    /// Creates a new backlight which is turned on if `pin` is high.
+    pub fn new(pin: P) -> Self {
+        Self {
+            pin,
+            active_low: false,
+        }
+    }
+
+    /// Creates a new backlight which is turned on if `pin` is low.
+    pub fn new_active_low(pin: P) -> Self {
+        Self {
+            pin,
+            active_low: true,
+        }
+    }
+
+    /// Releases the pin.
+    pub fn release(self) -> P {
+        self.pin
+    }
+}
+
+impl<P: OutputPin> Backlight for PinBacklight<P> {
+    type Error = P::Error;
+
+    fn set_level(&mut self, level: u8) -> Result<(), Self::Error> {
+        self.pin.set_state(((level != 0) != self.active_low).into())
+    }
+}
+
+/// Backlight which is dimmed using PWM.
+///
+/// The level is converted into a duty cycle, which means that `255` sets the
+/// duty cycle to 100%.
+pub struct PwmBacklight<P> {
+    pwm: P,
+    inverted: bool,
+}
+
+impl<P: SetDutyCycle> PwmBacklight<P> {
+    /// Creates a new backlight with the brightness proportional to the duty cycle.
+    pub fn new(pwm: P) -> Self {
+        Self {
+            pwm,
+            inverted: false,
+        }
+    }
+
+    /// Creates a new backlight with the brightness inversely proportional to
+    /// the duty cycle.
+    pub fn new_inverted(pwm: P) -> Self {
+        Self {
+            pwm,
+            inverted: true,
+        }
+    }
+
+    /// Releases the PWM channel.
+    pub fn release(self) -> P {
+        self.pwm
+    }
+}
+
+impl<P: SetDutyCycle> Backlight for PwmBacklight<P> {
+    type Error = P::Error;
+
+    fn set_level(&mut self, level: u8) -> Result<(), Self::Error> {
+        let level = if self.inverted { 255 - level } else { level };
+
+        self.pwm.set_duty_cycle_fraction(level.into(), 255)
+    }
+}
+
+impl<DI, M, RST, BL, PWR> Display<DI, M, RST, BL, PWR>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+    PWR: OutputPin,
+{
+    /// Sets the backlight level.
+    ///
+    /// `0` turns the backlight off and `255` sets it to the max brightness.
+    /// The backlight is kept off during [`init`](crate::Builder::init) to
+    /// make sure that the uninitialized framebuffer isn't visible. This method
+    /// should be used to turn on the backlight after the first frame was
+    /// drawn.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use embedded_graphics::{pixelcolor::Rgb565, prelude::*};
+    /// use mipidsi::{backlight::PinBacklight, Builder, models::ILI9341Rgb565};
+    ///
+    /// # let di = mipidsi::_mock::MockDisplayInterface;
+    /// # let bl = mipidsi::_mock::MockOutputPin;
+    /// # let mut delay = mipidsi::_mock::MockDelay;
+    /// let mut display = Builder::new(ILI9341Rgb565, di)
+    ///     .backlight(PinBacklight::new(bl))
+    ///     .init(&mut delay)
+    ///     .unwrap();
+    ///
+    /// display.clear(Rgb565::BLACK).unwrap();
+    /// display.set_backlight(255).unwrap();
+    /// ```
+    pub fn set_backlight(&mut self, level: u8) -> Result<(), BL::Error> {
+        self.backlight_level = level;
+        match &mut self.backlight {
+            Some(backlight) if !self.state.sleeping => backlight.set_level(level),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the backlight level.
+    pub fn backlight_level(&self) -> u8 {
+        self.backlight_level
+    }
+
+    /// Fades the backlight to the given level.
+    ///
+    /// The level is changed in steps of at least 10 ms over `duration_ms`
+    /// milliseconds, with the remainder spread across the steps, and a fade
+    /// shorter than 10 ms is done in a single step. This method blocks until
+    /// the fade is complete. Backlights which can't be
+    /// dimmed, like [`PinBacklight`], are turned on by the first step with a
+    /// non zero level and are only turned off by the last step of a fade to
+    /// `0`.
+    pub fn fade_backlight<D: DelayNs>(
+        &mut self,
+        level: u8,
+        duration_ms: u32,
+        delay: &mut D,
+    ) -> Result<(), BL::Error> {
+        let start = i32::from(self.backlight_level);
+        let end = i32::from(level);
+        let steps = (duration_ms / FADE_STEP_MS).max(1);
+        let duration_us = u64::from(duration_ms) * 1000;
+
+        for step in 1..=steps {
+            // Step delays differ by at most 1 µs to sum up to the whole duration
+            let elapsed = |step: u32| duration_us * u64::from(step) / u64::from(steps);
+            delay.delay_us((elapsed(step) - elapsed(step - 1)) as u32);
+
+            let level = start + (end - start) * step as i32 / steps as i32;
+            self.set_backlight(level as u8)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        _mock::{MockBacklight, MockDelay, MockDisplayInterface},
+        models::ILI9341Rgb565,
+        Builder,
+    };
+
+    /// Delay which sums up the requested delays.
+    struct SumDelay(u64);
+
+    impl DelayNs for SumDelay {
+        fn delay_ns(&mut self, ns: u32) {
+            self.0 += u64::from(ns);
+        }
+    }
+
+    #[test]
+    fn backlight_is_sequenced() {
+        let level = core::cell::Cell::new(255);
+        let mut display = Builder::new(ILI9341Rgb565, MockDisplayInterface)
+            .backlight(MockBacklight(&level))
+            .init(&mut MockDelay)
+            .unwrap();
+        assert_eq!(level.get(), 0);
+
+        display.set_backlight(200).unwrap();
+        assert_eq!(level.get(), 200);
+
+        display.sleep(&mut MockDelay).unwrap();
+        assert_eq!(level.get(), 0);
+        assert_eq!(display.backlight_level(), 200);
+
+        display.wake(&mut MockDelay).unwrap();
+        assert_eq!(level.get(), 200);
+
+        display.fade_backlight(100, 50, &mut MockDelay).unwrap();
+        assert_eq!(level.get(), 100);
+    }
+    #[test]
+    fn fade_takes_duration() {
+        let level = core::cell::Cell::new(0);
+        let mut display = Builder::new(ILI9341Rgb565, MockDisplayInterface)
+            .backlight(MockBacklight(&level))
+            .init(&mut MockDelay)
+            .unwrap();
+
+        for duration_ms in [0, 5, 25, 1001] {
+            let mut delay = SumDelay(0);
+            display
+                .fade_backlight(duration_ms as u8, duration_ms, &mut delay)
+                .unwrap();
+            assert_eq!(delay.0, u64::from(duration_ms) * 1_000_000);
+            assert_eq!(level.get(), duration_ms as u8);
+        }
+    }
+}
@@ -2,8 +2,10 @@
 //! Batch the pixels to be rendered into Pixel Rows and Pixel Blocks (contiguous Pixel Rows).
 //! This enables the pixels to be rendered efficiently as Pixel Blocks, which may be transmitted in a single Non-Blocking SPI request.
 use crate::{
+    backlight::Backlight,
     interface::{Interface, InterfacePixelFormat},
     models::Model,
+    options::VisibleArea,
     Display,
 };
 use embedded_graphics_core::prelude::*;
@@ -19,13 +21,15 @@ where
     fn draw_batch(&mut self, item_pixels: I) -> Result<(), DI::Error>;
 }
 
-impl<DI, M, RST, I> DrawBatch<DI, M, I> for Display<DI, M, RST>
+impl<DI, M, RST, BL, PWR, I> DrawBatch<DI, M, I> for Display<DI, M, RST, BL, PWR>
 where
     DI: Interface,
     M: Model,
     M::ColorFormat: InterfacePixelFormat<DI::Word>,
     I: IntoIterator<Item = Pixel<M::ColorFormat>>,
     RST: OutputPin,
+    BL: Backlight,
+    PWR: OutputPin,
 {
     fn draw_batch(&mut self, item_pixels: I) -> Result<(), DI::Error> {
         //  Get the pixels for the item to be rendered.
@@ -45,7 +49,20 @@ where
         } in blocks
         {
             //  Render the Pixel Block.
-            self.set_pixels(x_left, y_top, x_right, y_bottom, colors)?;
+            if self.options.visible_area == VisibleArea::Full {
+                self.set_pixels(x_left, y_top, x_right, y_bottom, colors)?;
+            } else {
+                //  Trim each row of the Pixel Block to the visible columns.
+                let width = usize::from(x_right - x_left) + 1;
+                for (y, row) in (y_top..=y_bottom).zip(colors.chunks(width)) {
+                    if let Some((start, end)) =
+                        self.visible_span(y.into(), x_left.into(), x_right.into())
+                    {
+                        let row = &row[usize::from(start - x_left)..=usize::from(end - x_left)];
+                        self.set_pixels(start, y, end, y, row.iter().copied())?;
+                    }
+                }
+            }
 
             //  Dump out the Pixel Blocks for the square in test_display()
             /* if x_left >= 60 && x_left <= 150 && x_right >= 60 && x_right <= 150 && y_top >= 60 && y_top <= 150 && y_bottom >= 60 && y_bottom <= 150 {
@@ -0,0 +1,199 @@
+use embedded_hal::digital::OutputPin;
+
+use crate::{
+    backlight::Backlight,
+    dcs::{self, DcsCommand, DcsReadCommand, InterfaceExt, ReadInterfaceExt},
+    interface::{Interface, InterfacePixelFormat, ReadInterface},
+    models::Model,
+    options::{BrightnessControl, CabcMode},
+    Display,
+};
+
+/// Error returned by the brightness control methods.
+#[derive(Debug)]
+pub enum BrightnessError<DI> {
+    /// Error caused by the display interface.
+    Interface(DI),
+    /// The display model doesn't support brightness control.
+    ///
+    /// See [`Model::BRIGHTNESS_CONTROL`].
+    Unsupported,
+    /// The display was powered off using [`Display::power_off`].
+    PoweredOff,
+}
+
+impl<DI, M, RST, BL, PWR> Display<DI, M, RST, BL, PWR>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+    PWR: OutputPin,
+{
+    /// Sets the display brightness.
+    ///
+    /// `0` is the lowest and `255` the highest brightness. The brightness is
+    /// only used if the brightness control block is enabled, see
+    /// [`set_brightness_control`](Self::set_brightness_control).
+    ///
+    /// Returns [`BrightnessError::Unsupported`] if the display model doesn't
+    /// support brightness control.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mipidsi::{Builder, models::RM67162};
+    ///
+    /// # let di = mipidsi::_mock::MockDisplayInterface;
+    /// # let mut delay = mipidsi::_mock::MockDelay;
+    /// let mut display = Builder::new(RM67162, di).init(&mut delay).unwrap();
+    ///
+    /// display.set_brightness(64).unwrap();
+    /// ```
+    pub fn set_brightness(&mut self, brightness: u8) -> Result<(), BrightnessError<DI::Error>> {
+        self.write_brightness_command(dcs::WriteDisplayBrightness::new(brightness))
+    }
+
+    /// Configures the brightness control block.
+    ///
+    /// Returns [`BrightnessError::Unsupported`] if the display model doesn't
+    /// support brightness control.
+    pub fn set_brightness_control(
+        &mut self,
+        control: BrightnessControl,
+    ) -> Result<(), BrightnessError<DI::Error>> {
+        self.write_brightness_command(dcs::WriteCtrlDisplay::new(control))
+    }
+
+    /// Sets the content adaptive brightness control (CABC) mode.
+    ///
+    /// CABC reduces the power consumption by adjusting the brightness based on
+    /// the displayed content.
+    ///
+    /// Returns [`BrightnessError::Unsupported`] if the display model doesn't
+    /// support brightness control.
+    pub fn set_cabc_mode(&mut self, mode: CabcMode) -> Result<(), BrightnessError<DI::Error>> {
+        self.write_brightness_command(dcs::WriteCabc::new(mode))
+    }
+
+    fn write_brightness_command(
+        &mut self,
+        command: impl DcsCommand,
+    ) -> Result<(), BrightnessError<DI::Error>> {
+        if !M::BRIGHTNESS_CONTROL {
+            return Err(BrightnessError::Unsupported);
+        }
+        if !self.state.powered {
+            return Err(BrightnessError::PoweredOff);
+        }
+
+        self.di
+            .write_command(command)
+            .map_err(BrightnessError::Interface)
+    }
+}
+
+impl<DI, M, RST, BL, PWR> Display<DI, M, RST, BL, PWR>
+where
+    DI: ReadInterface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+    PWR: OutputPin,
+{
+    /// Reads the display brightness using the Read Display Brightness (0x52)
+    /// command.
+    ///
+    /// Returns [`BrightnessError::Unsupported`] if the display model doesn't
+    /// support brightness control.
+    pub fn read_brightness(&mut self) -> Result<u8, BrightnessError<DI::Error>> {
+        self.read_brightness_command(dcs::ReadDisplayBrightness)
+    }
+
+    /// Reads the brightness control block configuration using the Read CTRL
+    /// Display (0x54) command.
+    ///
+    /// Returns [`BrightnessError::Unsupported`] if the display model doesn't
+    /// support brightness control.
+    pub fn read_ctrl_display(&mut self) -> Result<BrightnessControl, BrightnessError<DI::Error>> {
+        self.read_brightness_command(dcs::ReadCtrlDisplay)
+    }
+
+    /// Reads the content adaptive brightness control (CABC) mode using the Read
+    /// Content Adaptive Brightness Control (0x56) command.
+    ///
+    /// Returns [`BrightnessError::Unsupported`] if the display model doesn't
+    /// support brightness control.
+    pub fn read_cabc(&mut self) -> Result<CabcMode, BrightnessError<DI::Error>> {
+        self.read_brightness_command(dcs::ReadCabc)
+    }
+
+    fn read_brightness_command<C: DcsReadCommand>(
+        &mut self,
+        command: C,
+    ) -> Result<C::Response, BrightnessError<DI::Error>> {
+        if !M::BRIGHTNESS_CONTROL {
+            return Err(BrightnessError::Unsupported);
+        }
+        if !self.state.powered {
+            return Err(BrightnessError::PoweredOff);
+        }
+
+        self.di
+            .read_dcs_command(command)
+            .map_err(BrightnessError::Interface)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cell::RefCell;
+
+    use super::*;
+    use crate::{
+        _mock::{new_mock_display, MockDelay, MockInterface, MockInterfaceState},
+        models::RM67162,
+        Builder,
+    };
+
+    #[test]
+    fn reads_brightness_registers() {
+        let state = RefCell::new(MockInterfaceState::new());
+        {
+            let read_values = &mut state.borrow_mut().read_values;
+            read_values[usize::from(dcs::ReadDisplayBrightness.instruction())][0] = 0xAF;
+            read_values[usize::from(dcs::ReadCtrlDisplay.instruction())][0] = 0b0010_1100;
+            read_values[usize::from(dcs::ReadCabc.instruction())][0] = 0b10;
+        }
+        let mut display = Builder::new(RM67162, MockInterface(&state))
+            .init(&mut MockDelay)
+            .unwrap();
+
+        assert_eq!(display.read_brightness().unwrap(), 0xAF);
+        assert_eq!(
+            display.read_ctrl_display().unwrap(),
+            BrightnessControl {
+                brightness_control: true,
+                dimming: true,
+                backlight: true,
+            }
+        );
+        assert_eq!(display.read_cabc().unwrap(), CabcMode::StillPicture);
+    }
+
+    #[test]
+    fn unsupported_models_return_error() {
+        let mut display = new_mock_display();
+
+        assert!(matches!(
+            display.set_brightness(128),
+            Err(BrightnessError::Unsupported)
+        ));
+        assert!(matches!(
+            display.set_cabc_mode(CabcMode::MovingImage),
+            Err(BrightnessError::Unsupported)
+        ));
+    }
+}
@@ -3,10 +3,14 @@
 use embedded_hal::digital;
 use embedded_hal::{delay::DelayNs, digital::OutputPin};
 
+use crate::backlight::{Backlight, NoBacklight};
 use crate::interface::{Interface, InterfacePixelFormat};
-use crate::{dcs::InterfaceExt, models::Model, Display};
+use crate::power::DEFAULT_POWER_SETTLE_US;
+use crate::{dcs::InterfaceExt, models::Model, Display, InitSequence};
 
-use crate::options::{ColorInversion, ColorOrder, ModelOptions, Orientation, RefreshOrder};
+use crate::options::{
+    ColorInversion, ColorOrder, ModelOptions, Orientation, RefreshOrder, VisibleArea,
+};
 
 /// Builder for [Display] instances.
 ///
@@ -26,7 +30,7 @@ use crate::options::{ColorInversion, ColorOrder, ModelOptions, Orientation, Refr
 ///     .display_size(320, 240)
 ///     .init(&mut delay).unwrap();
 /// ```
-pub struct Builder<DI, MODEL, RST>
+pub struct Builder<DI, MODEL, RST, BL = NoBacklight, PWR = NoPowerPin>
 where
     DI: Interface,
     MODEL: Model,
@@ -35,10 +39,13 @@ where
     di: DI,
     model: MODEL,
     rst: Option<RST>,
+    backlight: Option<BL>,
+    power: Option<PWR>,
+    power_settle_us: u32,
     options: ModelOptions,
 }
 
-impl<DI, MODEL> Builder<DI, MODEL, NoResetPin>
+impl<DI, MODEL> Builder<DI, MODEL, NoResetPin, NoBacklight, NoPowerPin>
 where
     DI: Interface,
     MODEL: Model,
@@ -53,17 +60,22 @@ where
             di,
             model,
             rst: None,
+            backlight: None,
+            power: None,
+            power_settle_us: DEFAULT_POWER_SETTLE_US,
             options: ModelOptions::full_size::<MODEL>(),
         }
     }
 }
 
-impl<DI, MODEL, RST> Builder<DI, MODEL, RST>
+impl<DI, MODEL, RST, BL, PWR> Builder<DI, MODEL, RST, BL, PWR>
 where
     DI: Interface,
     MODEL: Model,
     MODEL::ColorFormat: InterfacePixelFormat<DI::Word>,
     RST: OutputPin,
+    BL: Backlight,
+    PWR: OutputPin,
 {
     ///
     /// Sets the invert color flag
@@ -123,6 +135,15 @@ where
         self
     }
 
+    ///
+    /// Sets the [VisibleArea] for non rectangular displays
+    ///
+    #[must_use]
+    pub fn visible_area(mut self, visible_area: VisibleArea) -> Self {
+        self.options.visible_area = visible_area;
+        self
+    }
+
     /// Sets the reset pin.
     ///
     /// ### WARNING
@@ -130,15 +151,110 @@ where
     /// If it wasn't provided the user needs to ensure this is the case.
     ///
     #[must_use]
-    pub fn reset_pin<RST2: OutputPin>(self, rst: RST2) -> Builder<DI, MODEL, RST2> {
+    pub fn reset_pin<RST2: OutputPin>(self, rst: RST2) -> Builder<DI, MODEL, RST2, BL, PWR> {
         Builder {
             di: self.di,
             model: self.model,
             rst: Some(rst),
+            backlight: self.backlight,
+            power: self.power,
+            power_settle_us: self.power_settle_us,
+            options: self.options,
+        }
+    }
+
+    /// Sets the backlight.
+    ///
+    /// The backlight is turned off before the display is reset and is kept off
+    /// after [`init`](Self::init), to make sure that the uninitialized
+    /// framebuffer isn't visible. Use [`Display::set_backlight`] to turn on the
+    /// backlight after the first frame was drawn.
+    ///
+    /// The backlight is also turned off by [`Display::sleep`] and restored by
+    /// [`Display::wake`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mipidsi::{backlight::PwmBacklight, Builder, models::ILI9341Rgb565};
+    ///
+    /// # let di = mipidsi::_mock::MockDisplayInterface;
+    /// # let pwm = mipidsi::_mock::MockPwm;
+    /// # let mut delay = mipidsi::_mock::MockDelay;
+    /// let mut display = Builder::new(ILI9341Rgb565, di)
+    ///     .backlight(PwmBacklight::new(pwm))
+    ///     .init(&mut delay)
+    ///     .unwrap();
+    ///
+    /// display.fade_backlight(255, 500, &mut delay).unwrap();
+    /// ```
+    #[must_use]
+    pub fn backlight<BL2: Backlight>(self, backlight: BL2) -> Builder<DI, MODEL, RST, BL2, PWR> {
+        Builder {
+            di: self.di,
+            model: self.model,
+            rst: self.rst,
+            backlight: Some(backlight),
+            power: self.power,
+            power_settle_us: self.power_settle_us,
+            options: self.options,
+        }
+    }
+
+    /// Sets the power enable pin.
+    ///
+    /// The pin is used to switch the supply of the display, e.g. by using a
+    /// load switch. It's set high before the display is reset during
+    /// [`init`](Self::init) and [`Display::power_on`] and set low by
+    /// [`Display::power_off`].
+    ///
+    /// After the pin is set high the driver waits for the supply to settle
+    /// before the display is reset. The settle time defaults to 10 ms and can be
+    /// changed with [`power_settle_time`](Self::power_settle_time).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mipidsi::{Builder, models::ILI9341Rgb565};
+    ///
+    /// # let di = mipidsi::_mock::MockDisplayInterface;
+    /// # let rst = mipidsi::_mock::MockOutputPin;
+    /// # let pwr = mipidsi::_mock::MockOutputPin;
+    /// # let mut delay = mipidsi::_mock::MockDelay;
+    /// let mut display = Builder::new(ILI9341Rgb565, di)
+    ///     .reset_pin(rst)
+    ///     .power_pin(pwr)
+    ///     .power_settle_time(50_000)
+    ///     .init(&mut delay)
+    ///     .unwrap();
+    ///
+    /// display.power_off(&mut delay).unwrap();
+    /// display.power_on(&mut delay).unwrap();
+    /// ```
+    #[must_use]
+    pub fn power_pin<PWR2: OutputPin>(self, power: PWR2) -> Builder<DI, MODEL, RST, BL, PWR2> {
+        Builder {
+            di: self.di,
+            model: self.model,
+            rst: self.rst,
+            backlight: self.backlight,
+            power: Some(power),
+            power_settle_us: self.power_settle_us,
             options: self.options,
         }
     }
 
+    /// Sets the time in microseconds the supply needs to settle after the
+    /// display was powered on.
+    ///
+    /// This setting is only used if a power pin was set using
+    /// [`power_pin`](Self::power_pin).
+    #[must_use]
+    pub fn power_settle_time(mut self, settle_us: u32) -> Self {
+        self.power_settle_us = settle_us;
+        self
+    }
+
     ///
     /// Consumes the builder to create a new [Display] with an optional reset [OutputPin].
     /// Blocks using the provided [DelayNs] `delay_source` to perform the display initialization.
@@ -150,9 +266,59 @@ where
     /// and [`display_offset`](Self::display_offset) settings is (partially)
     /// outside the framebuffer.
     pub fn init(
-        mut self,
+        self,
         delay_source: &mut impl DelayNs,
-    ) -> Result<Display<DI, MODEL, RST>, InitError<DI::Error, RST::Error>> {
+    ) -> Result<
+        Display<DI, MODEL, RST, BL, PWR>,
+        InitError<DI::Error, RST::Error, BL::Error, PWR::Error>,
+    > {
+        let mut display = self.build();
+        display.initialize(delay_source)?;
+
+        Ok(display)
+    }
+
+    ///
+    /// Consumes the builder to create an [InitSequence] which initializes the
+    /// display without blocking.
+    ///
+    /// The initialization is performed step by step by calling
+    /// [InitSequence::poll] with the current time, until it returns the
+    /// initialized [Display]. This allows the display to be initialized in a
+    /// superloop without blocking for the delays required by the display
+    /// controller.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the area defined by the [`display_size`](Self::display_size)
+    /// and [`display_offset`](Self::display_offset) settings is (partially)
+    /// outside the framebuffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::task::Poll;
+    /// use mipidsi::{Builder, models::ILI9341Rgb565};
+    ///
+    /// # let di = mipidsi::_mock::MockDisplayInterface;
+    /// # let mut now_us = 0;
+    /// let mut init = Builder::new(ILI9341Rgb565, di).init_nonblocking();
+    ///
+    /// let display = loop {
+    ///     if let Poll::Ready(display) = init.poll(now_us) {
+    ///         break display.unwrap();
+    ///     }
+    ///
+    ///     // do other work
+    /// #   now_us += 1_000;
+    /// };
+    /// ```
+    pub fn init_nonblocking(self) -> InitSequence<DI, MODEL, RST, BL, PWR> {
+        InitSequence::new(self.build())
+    }
+
+    /// Creates an uninitialized display.
+    fn build(self) -> Display<DI, MODEL, RST, BL, PWR> {
         let to_u32 = |(a, b)| (u32::from(a), u32::from(b));
         let (width, height) = to_u32(self.options.display_size);
         let (offset_x, offset_y) = to_u32(self.options.display_offset);
@@ -160,6 +326,54 @@ where
         assert!(width + offset_x <= max_width);
         assert!(height + offset_y <= max_height);
 
+        Display {
+            di: self.di,
+            model: self.model,
+            rst: self.rst,
+            backlight: self.backlight,
+            backlight_level: 0,
+            power: self.power,
+            power_settle_us: self.power_settle_us,
+            options: self.options,
+            madctl: crate::dcs::SetAddressMode::default(),
+            state: crate::PowerState::RESET,
+            scroll_area: crate::dcs::SetScrollArea::new(0, MODEL::FRAMEBUFFER_SIZE.1, 0),
+            scroll_start: 0,
+            tearing_effect: None,
+            tear_scanline: None,
+            draw_page: None,
+            partial_area: None,
+            pending: None,
+        }
+    }
+}
+
+impl<DI, M, RST, BL, PWR> Display<DI, M, RST, BL, PWR>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+    PWR: OutputPin,
+{
+    /// Powers on, resets and initializes the display.
+    ///
+    /// The backlight is turned off before the display is reset and the cached
+    /// controller state is reset to the state after initialization.
+    pub(crate) fn initialize(
+        &mut self,
+        delay_source: &mut impl DelayNs,
+    ) -> Result<(), InitError<DI::Error, RST::Error, BL::Error, PWR::Error>> {
+        if let Some(backlight) = &mut self.backlight {
+            backlight.set_level(0).map_err(InitError::Backlight)?;
+        }
+
+        if let Some(power) = &mut self.power {
+            power.set_high().map_err(InitError::PowerPin)?;
+            delay_source.delay_us(self.power_settle_us);
+        }
+
         match self.rst {
             Some(ref mut rst) => {
                 rst.set_low().map_err(InitError::ResetPin)?;
@@ -176,27 +390,40 @@ where
             .model
             .init(&mut self.di, delay_source, &self.options)
             .map_err(InitError::Interface)?;
+        self.finish_init(madctl);
 
-        let display = Display {
-            di: self.di,
-            model: self.model,
-            rst: self.rst,
-            options: self.options,
-            madctl,
-            sleeping: false, // TODO: init should lock state
-        };
+        Ok(())
+    }
 
-        Ok(display)
+    /// Updates the cached controller state after the display was initialized.
+    pub(crate) fn finish_init(&mut self, madctl: crate::dcs::SetAddressMode) {
+        self.madctl = madctl;
+        // All models exit sleep mode and turn on the display output during init
+        self.state = crate::PowerState {
+            sleeping: false,
+            display_on: true,
+            ..crate::PowerState::RESET
+        };
+        self.scroll_area = crate::dcs::SetScrollArea::new(0, M::FRAMEBUFFER_SIZE.1, 0);
+        self.scroll_start = 0;
+        self.tearing_effect = None;
+        self.tear_scanline = None;
+        self.draw_page = None;
+        self.pending = None;
     }
 }
 
 /// Error returned by [`Builder::init`].
 #[derive(Debug)]
-pub enum InitError<DI, P> {
+pub enum InitError<DI, P, BL = core::convert::Infallible, PWR = core::convert::Infallible> {
     /// Error caused by the display interface.
     Interface(DI),
     /// Error caused by the reset pin's [`OutputPin`](embedded_hal::digital::OutputPin) implementation.
     ResetPin(P),
+    /// Error caused by the backlight.
+    Backlight(BL),
+    /// Error caused by the power pin's [`OutputPin`](embedded_hal::digital::OutputPin) implementation.
+    PowerPin(PWR),
 }
 
 /// Marker type for no reset pin.
@@ -216,6 +443,23 @@ impl digital::ErrorType for NoResetPin {
     type Error = core::convert::Infallible;
 }
 
+/// Marker type for no power pin.
+pub enum NoPowerPin {}
+
+impl digital::OutputPin for NoPowerPin {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl digital::ErrorType for NoPowerPin {
+    type Error = core::convert::Infallible;
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
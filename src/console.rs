@@ -0,0 +1,375 @@
+//! Hardware scrolling text console.
+
+use embedded_graphics::{
+    geometry::{Dimensions, Point, Size},
+    mono_font::MonoTextStyle,
+    pixelcolor::RgbColor,
+    primitives::Rectangle,
+    text::{Baseline, Text},
+    Drawable,
+};
+use embedded_graphics_core::draw_target::DrawTarget;
+use embedded_hal::digital::OutputPin;
+
+use crate::{
+    backlight::{Backlight, NoBacklight},
+    interface::{Interface, InterfacePixelFormat},
+    models::Model,
+    Display, NoPowerPin,
+};
+
+/// Error returned by [`Console::new`].
+#[derive(Debug)]
+pub enum ConsoleError<DI> {
+    /// Error caused by the display interface.
+    Interface(DI),
+    /// The area between the header and footer can't fit at least a single line
+    /// of text.
+    TooSmall,
+}
+
+/// Text console which uses hardware scrolling.
+///
+/// The console renders monospace text into the scroll region of the display.
+/// When a new line is started at the bottom of the console, the display is
+/// scrolled by updating the vertical scroll start address (VSCRSAD) and only
+/// the newly exposed line is cleared, instead of redrawing the whole screen.
+///
+/// Optional header and footer areas at the top and bottom of the display are
+/// excluded from scrolling and can be drawn to using
+/// [`display`](Self::display) together with the [`header_area`](Self::header_area)
+/// and [`footer_area`](Self::footer_area) methods.
+///
+/// Hardware scrolling is always relative to the default display orientation,
+/// which means that the console only works correctly if the display uses the
+/// default [`Orientation`](crate::options::Orientation).
+///
+/// # Examples
+///
+/// ```
+/// use core::fmt::Write;
+/// use embedded_graphics::{mono_font::{ascii::FONT_6X10, MonoTextStyle}, pixelcolor::Rgb565, prelude::*};
+/// use mipidsi::console::Console;
+///
+/// # let mut display = mipidsi::_mock::new_mock_display();
+/// let style = MonoTextStyle::new(&FONT_6X10, Rgb565::GREEN);
+/// let mut console = Console::new(&mut display, style, 20, 0).unwrap();
+///
+/// writeln!(console, "booting...").unwrap();
+/// ```
+pub struct Console<'a, DI, M, RST, BL = NoBacklight, PWR = NoPowerPin>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+    PWR: OutputPin,
+{
+    display: &'a mut Display<DI, M, RST, BL, PWR>,
+    style: MonoTextStyle<'a, M::ColorFormat>,
+    background: M::ColorFormat,
+    header_height: u16,
+    footer_height: u16,
+    char_size: Size,
+    columns: u16,
+    lines: u16,
+    // First row of the scroll region in framebuffer coordinates
+    scroll_top: u16,
+    // Current scroll offset in pixels relative to `scroll_top`
+    scroll: u16,
+    column: u16,
+    line: u16,
+    // Last error returned by the display, used by the `fmt::Write` impl
+    error: Option<DI::Error>,
+}
+
+impl<'a, DI, M, RST, BL, PWR> Console<'a, DI, M, RST, BL, PWR>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+    PWR: OutputPin,
+{
+    /// Creates a new console.
+    ///
+    /// The scroll region is set up between the header and footer areas and
+    /// cleared with the background color of `style`, or black if the style
+    /// doesn't have a background color.
+    ///
+    /// Returns [`ConsoleError::TooSmall`] if the area between the header and
+    /// footer can't fit at least a single line of text.
+    pub fn new(
+        display: &'a mut Display<DI, M, RST, BL, PWR>,
+        mut style: MonoTextStyle<'a, M::ColorFormat>,
+        header_height: u16,
+        footer_height: u16,
+    ) -> Result<Self, ConsoleError<DI::Error>> {
+        let background = style.background_color.unwrap_or(M::ColorFormat::BLACK);
+        style.background_color = Some(background);
+
+        let font = style.font;
+        let char_size = Size::new(
+            font.character_size.width + font.character_spacing,
+            font.character_size.height,
+        );
+
+        let size = display.bounding_box().size;
+        let region_height = header_height
+            .checked_add(footer_height)
+            .and_then(|fixed_height| (size.height as u16).checked_sub(fixed_height))
+            .ok_or(ConsoleError::TooSmall)?;
+        let lines = region_height / char_size.height as u16;
+        let columns = (size.width / char_size.width) as u16;
+        if lines == 0 || columns == 0 {
+            return Err(ConsoleError::TooSmall);
+        }
+
+        // Rows which don't fit a whole line are added to the bottom fixed area
+        let scroll_top = display.options.display_offset.1 + header_height;
+        let scroll_height = lines * char_size.height as u16;
+        let bottom_fixed_area = M::FRAMEBUFFER_SIZE.1 - scroll_top - scroll_height;
+
+        let mut console = Self {
+            display,
+            style,
+            background,
+            header_height,
+            footer_height,
+            char_size,
+            columns,
+            lines,
+            scroll_top,
+            scroll: 0,
+            column: 0,
+            line: 0,
+            error: None,
+        };
+
+        console
+            .display
+            .set_vertical_scroll_region(scroll_top, bottom_fixed_area)
+            .map_err(ConsoleError::Interface)?;
+        console.clear().map_err(ConsoleError::Interface)?;
+
+        Ok(console)
+    }
+
+    /// Returns a mutable reference to the display.
+    ///
+    /// This can be used to draw into the header and footer areas. Drawing into
+    /// the scroll region will be overwritten by the console.
+    pub fn display(&mut self) -> &mut Display<DI, M, RST, BL, PWR> {
+        self.display
+    }
+
+    /// Returns the header area.
+    pub fn header_area(&self) -> Rectangle {
+        Rectangle::new(
+            Point::zero(),
+            Size::new(self.display_width(), self.header_height.into()),
+        )
+    }
+
+    /// Returns the footer area.
+    pub fn footer_area(&self) -> Rectangle {
+        let height = self.display.bounding_box().size.height;
+        let footer_height = u32::from(self.footer_height);
+
+        Rectangle::new(
+            Point::new(0, (height - footer_height) as i32),
+            Size::new(self.display_width(), footer_height),
+        )
+    }
+
+    /// Returns the number of text columns and lines.
+    pub fn text_size(&self) -> (u16, u16) {
+        (self.columns, self.lines)
+    }
+
+    /// Clears the console and moves the cursor to the top left corner.
+    pub fn clear(&mut self) -> Result<(), DI::Error> {
+        self.scroll = 0;
+        self.column = 0;
+        self.line = 0;
+        self.display
+            .set_vertical_scroll_offset(self.scroll_top + self.scroll)?;
+
+        let area = Rectangle::new(
+            Point::new(0, self.header_height.into()),
+            Size::new(
+                self.display_width(),
+                u32::from(self.lines) * self.char_size.height,
+            ),
+        );
+        self.display.fill_solid(&area, self.background)
+    }
+
+    /// Writes text to the console.
+    ///
+    /// `\n` starts a new line and `\r` moves the cursor to the start of the
+    /// current line. Lines which are longer than the console width are wrapped.
+    pub fn write_str(&mut self, text: &str) -> Result<(), DI::Error> {
+        for c in text.chars() {
+            self.write_char(c)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes a single character to the console.
+    pub fn write_char(&mut self, c: char) -> Result<(), DI::Error> {
+        match c {
+            '\n' => self.new_line(),
+            '\r' => {
+                self.column = 0;
+                Ok(())
+            }
+            _ => {
+                if self.column >= self.columns {
+                    self.new_line()?;
+                }
+
+                let position = Point::new(
+                    (u32::from(self.column) * self.char_size.width) as i32,
+                    self.line_y(self.line),
+                );
+                let mut buffer = [0; 4];
+                Text::with_baseline(
+                    c.encode_utf8(&mut buffer),
+                    position,
+                    self.style,
+                    Baseline::Top,
+                )
+                .draw(self.display)?;
+
+                self.column += 1;
+                Ok(())
+            }
+        }
+    }
+
+    /// Moves the cursor to the start of the next line.
+    ///
+    /// If the cursor is in the last line the display is scrolled by one line
+    /// and only the newly exposed line is cleared.
+    fn new_line(&mut self) -> Result<(), DI::Error> {
+        self.column = 0;
+
+        if self.line + 1 < self.lines {
+            self.line += 1;
+            return Ok(());
+        }
+
+        let line_height = self.char_size.height as u16;
+        self.scroll = (self.scroll + line_height) % (self.lines * line_height);
+        self.display
+            .set_vertical_scroll_offset(self.scroll_top + self.scroll)?;
+
+        let area = Rectangle::new(
+            Point::new(0, self.line_y(self.line)),
+            Size::new(self.display_width(), self.char_size.height),
+        );
+        self.display.fill_solid(&area, self.background)
+    }
+
+    /// Returns the display Y coordinate of a visible text line.
+    fn line_y(&self, line: u16) -> i32 {
+        let line_height = self.char_size.height as u16;
+        let offset = (line * line_height + self.scroll) % (self.lines * line_height);
+
+        i32::from(self.header_height + offset)
+    }
+
+    /// Returns the last display error which occurred while the console was
+    /// used through the [`core::fmt::Write`] trait.
+    pub fn take_error(&mut self) -> Option<DI::Error> {
+        self.error.take()
+    }
+
+    fn display_width(&self) -> u32 {
+        self.display.bounding_box().size.width
+    }
+}
+
+impl<DI, M, RST, BL, PWR> core::fmt::Write for Console<'_, DI, M, RST, BL, PWR>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+    PWR: OutputPin,
+{
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        Console::write_str(self, s).map_err(|e| {
+            self.error = Some(e);
+            core::fmt::Error
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics::{mono_font::ascii::FONT_6X10, pixelcolor::Rgb565};
+
+    use super::*;
+    use crate::_mock::new_mock_display;
+
+    #[test]
+    fn scrolls_at_last_line() {
+        let mut display = new_mock_display();
+        let style = MonoTextStyle::new(&FONT_6X10, Rgb565::WHITE);
+        let mut console = Console::new(&mut display, style, 20, 5).unwrap();
+
+        // 240x320 display with 295 rows for text
+        assert_eq!(console.text_size(), (40, 29));
+        assert_eq!(console.header_area().size, Size::new(240, 20));
+        assert_eq!(console.footer_area().top_left, Point::new(0, 315));
+
+        for _ in 0..28 {
+            console.write_str("line\n").unwrap();
+        }
+        assert_eq!(console.scroll, 0);
+        assert_eq!(console.line_y(console.line), 20 + 28 * 10);
+
+        // Starting a new line in the last line scrolls by a single line
+        console.write_str("line\n").unwrap();
+        assert_eq!(console.scroll, 10);
+        assert_eq!(console.line, 28);
+        assert_eq!(console.line_y(console.line), 20);
+        assert_eq!(console.line_y(0), 30);
+    }
+
+    #[test]
+    fn wraps_long_lines() {
+        let mut display = new_mock_display();
+        let style = MonoTextStyle::new(&FONT_6X10, Rgb565::WHITE);
+        let mut console = Console::new(&mut display, style, 0, 0).unwrap();
+
+        for _ in 0..41 {
+            console.write_char('x').unwrap();
+        }
+        assert_eq!((console.column, console.line), (1, 1));
+
+        console.write_char('\r').unwrap();
+        assert_eq!((console.column, console.line), (0, 1));
+    }
+
+    #[test]
+    fn rejects_too_small_area() {
+        let mut display = new_mock_display();
+        let style = MonoTextStyle::new(&FONT_6X10, Rgb565::WHITE);
+
+        assert!(matches!(
+            Console::new(&mut display, style, 160, 151),
+            Err(ConsoleError::TooSmall)
+        ));
+        assert!(matches!(
+            Console::new(&mut display, style, u16::MAX, 1),
+            Err(ConsoleError::TooSmall)
+        ));
+    }
+}
@@ -1,6 +1,6 @@
 //! MIPI DCS commands.
 
-use crate::interface::Interface;
+use crate::interface::{Interface, ReadInterface};
 
 #[macro_use]
 mod macros;
@@ -19,6 +19,18 @@ mod set_scroll_start;
 pub use set_scroll_start::*;
 mod set_tearing_effect;
 pub use set_tearing_effect::*;
+mod set_tear_scanline;
+pub use set_tear_scanline::*;
+mod set_partial_rows;
+pub use set_partial_rows::*;
+mod set_partial_columns;
+pub use set_partial_columns::*;
+mod write_brightness;
+pub use write_brightness::*;
+mod read_brightness;
+pub use read_brightness::*;
+mod read_status;
+pub use read_status::*;
 mod set_invert_mode;
 pub use set_invert_mode::*;
 
@@ -65,6 +77,42 @@ pub trait InterfaceExt: Interface {
 
 impl<T: Interface> InterfaceExt for T {}
 
+/// Common trait for DCS read commands.
+///
+/// The methods in this trait are used to decode the response of a DCS read
+/// command.
+pub trait DcsReadCommand {
+    /// Decoded response of the command.
+    type Response;
+
+    /// Returns the instruction code.
+    fn instruction(&self) -> u8;
+
+    /// Returns the number of response bytes, which must not exceed 4.
+    fn response_len(&self) -> usize;
+
+    /// Decodes the response bytes.
+    fn decode(&self, response: &[u8]) -> Self::Response;
+}
+
+/// An extension trait for [`ReadInterface`] with support for reading DCS
+/// commands.
+pub trait ReadInterfaceExt: ReadInterface {
+    /// Sends a DCS read command and returns the decoded response.
+    fn read_dcs_command<C: DcsReadCommand>(
+        &mut self,
+        command: C,
+    ) -> Result<C::Response, Self::Error> {
+        let mut response = [0; 4];
+        let response = &mut response[..command.response_len()];
+        self.read_command(command.instruction(), response)?;
+
+        Ok(command.decode(response))
+    }
+}
+
+impl<T: ReadInterface> ReadInterfaceExt for T {}
+
 // DCS commands that don't use any parameters
 
 dcs_basic_command!(
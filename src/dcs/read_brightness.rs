@@ -0,0 +1,107 @@
+//! Module for the RDDISBV, RDCTRLD and RDCABC brightness read instructions
+
+use crate::options::{BrightnessControl, CabcMode};
+
+use super::DcsReadCommand;
+
+/// Read Display Brightness
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReadDisplayBrightness;
+
+impl DcsReadCommand for ReadDisplayBrightness {
+    type Response = u8;
+
+    fn instruction(&self) -> u8 {
+        0x52
+    }
+
+    fn response_len(&self) -> usize {
+        1
+    }
+
+    fn decode(&self, response: &[u8]) -> Self::Response {
+        response[0]
+    }
+}
+
+/// Read CTRL Display
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReadCtrlDisplay;
+
+impl DcsReadCommand for ReadCtrlDisplay {
+    type Response = BrightnessControl;
+
+    fn instruction(&self) -> u8 {
+        0x54
+    }
+
+    fn response_len(&self) -> usize {
+        1
+    }
+
+    fn decode(&self, response: &[u8]) -> Self::Response {
+        BrightnessControl {
+            brightness_control: response[0] & 1 << 5 != 0,
+            dimming: response[0] & 1 << 3 != 0,
+            backlight: response[0] & 1 << 2 != 0,
+        }
+    }
+}
+
+/// Read Content Adaptive Brightness Control
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReadCabc;
+
+impl DcsReadCommand for ReadCabc {
+    type Response = CabcMode;
+
+    fn instruction(&self) -> u8 {
+        0x56
+    }
+
+    fn response_len(&self) -> usize {
+        1
+    }
+
+    fn decode(&self, response: &[u8]) -> Self::Response {
+        match response[0] & 0b11 {
+            0b00 => CabcMode::Off,
+            0b01 => CabcMode::UserInterface,
+            0b10 => CabcMode::StillPicture,
+            _ => CabcMode::MovingImage,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rdctrld_decodes_bits_properly() {
+        assert_eq!(ReadCtrlDisplay.instruction(), 0x54);
+        assert_eq!(
+            ReadCtrlDisplay.decode(&[0x2C]),
+            BrightnessControl {
+                brightness_control: true,
+                dimming: true,
+                backlight: true,
+            }
+        );
+        assert_eq!(
+            ReadCtrlDisplay.decode(&[0x20]),
+            BrightnessControl {
+                brightness_control: true,
+                dimming: false,
+                backlight: false,
+            }
+        );
+    }
+
+    #[test]
+    fn rdcabc_decodes_mode_properly() {
+        assert_eq!(ReadCabc.instruction(), 0x56);
+        assert_eq!(ReadCabc.decode(&[0x02]), CabcMode::StillPicture);
+        assert_eq!(ReadCabc.decode(&[0xFF]), CabcMode::MovingImage);
+    }
+}
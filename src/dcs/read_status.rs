@@ -0,0 +1,124 @@
+//! Module for the RDDST, RDDMADCTL and RDDCOLMOD status read instructions
+
+use super::DcsReadCommand;
+
+/// Display status returned by [`ReadDisplayStatus`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisplayStatus {
+    /// Idle mode is on.
+    pub idle: bool,
+    /// Partial mode is on.
+    pub partial: bool,
+    /// The display isn't in sleep mode.
+    pub sleep_out: bool,
+    /// Normal display mode is on.
+    pub normal_mode: bool,
+    /// Color inversion is on.
+    pub inverted: bool,
+    /// The display output is on.
+    pub display_on: bool,
+    /// The tearing effect output is on.
+    pub tearing_effect: bool,
+}
+
+/// Read Display Status
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReadDisplayStatus;
+
+impl DcsReadCommand for ReadDisplayStatus {
+    type Response = DisplayStatus;
+
+    fn instruction(&self) -> u8 {
+        0x09
+    }
+
+    fn response_len(&self) -> usize {
+        4
+    }
+
+    fn decode(&self, response: &[u8]) -> Self::Response {
+        DisplayStatus {
+            idle: response[1] & 1 << 3 != 0,
+            partial: response[1] & 1 << 2 != 0,
+            sleep_out: response[1] & 1 << 1 != 0,
+            normal_mode: response[1] & 1 != 0,
+            inverted: response[2] & 1 << 5 != 0,
+            display_on: response[2] & 1 << 2 != 0,
+            tearing_effect: response[2] & 1 << 1 != 0,
+        }
+    }
+}
+
+/// Read Display MADCTL
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReadMadctl;
+
+impl DcsReadCommand for ReadMadctl {
+    type Response = u8;
+
+    fn instruction(&self) -> u8 {
+        0x0B
+    }
+
+    fn response_len(&self) -> usize {
+        1
+    }
+
+    fn decode(&self, response: &[u8]) -> Self::Response {
+        response[0]
+    }
+}
+
+/// Read Display Pixel Format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReadPixelFormat;
+
+impl DcsReadCommand for ReadPixelFormat {
+    type Response = u8;
+
+    fn instruction(&self) -> u8 {
+        0x0C
+    }
+
+    fn response_len(&self) -> usize {
+        1
+    }
+
+    fn decode(&self, response: &[u8]) -> Self::Response {
+        response[0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rddst_decodes_bits_properly() {
+        assert_eq!(ReadDisplayStatus.instruction(), 0x09);
+        assert_eq!(
+            ReadDisplayStatus.decode(&[0x80, 0b0101_0011, 0b0000_0100, 0]),
+            DisplayStatus {
+                idle: false,
+                partial: false,
+                sleep_out: true,
+                normal_mode: true,
+                inverted: false,
+                display_on: true,
+                tearing_effect: false,
+            }
+        );
+        assert_eq!(
+            ReadDisplayStatus.decode(&[0, 0b0000_1100, 0b0010_0010, 0]),
+            DisplayStatus {
+                idle: true,
+                partial: true,
+                sleep_out: false,
+                normal_mode: false,
+                inverted: true,
+                display_on: false,
+                tearing_effect: true,
+            }
+        );
+    }
+}
@@ -0,0 +1,48 @@
+//! Module for the partial columns instruction constructors
+
+use super::DcsCommand;
+
+/// Set Partial Columns
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetPartialColumns {
+    start_column: u16,
+    end_column: u16,
+}
+
+impl SetPartialColumns {
+    /// Creates a new Set Partial Columns command.
+    pub const fn new(start_column: u16, end_column: u16) -> Self {
+        Self {
+            start_column,
+            end_column,
+        }
+    }
+}
+
+impl DcsCommand for SetPartialColumns {
+    fn instruction(&self) -> u8 {
+        0x31
+    }
+
+    fn fill_params_buf(&self, buffer: &mut [u8]) -> usize {
+        buffer[0..2].copy_from_slice(&self.start_column.to_be_bytes());
+        buffer[2..4].copy_from_slice(&self.end_column.to_be_bytes());
+
+        4
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn partial_columns_fills_data_properly() {
+        let partial_columns = SetPartialColumns::new(16, 300);
+
+        let mut buffer = [0u8; 4];
+        assert_eq!(partial_columns.instruction(), 0x31);
+        assert_eq!(partial_columns.fill_params_buf(&mut buffer), 4);
+        assert_eq!(buffer, [0, 0x10, 0x1, 0x2C]);
+    }
+}
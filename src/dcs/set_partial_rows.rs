@@ -0,0 +1,45 @@
+//! Module for the PTLAR partial area instruction constructors
+
+use super::DcsCommand;
+
+/// Set Partial Rows
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetPartialRows {
+    start_row: u16,
+    end_row: u16,
+}
+
+impl SetPartialRows {
+    /// Creates a new Set Partial Rows command.
+    pub const fn new(start_row: u16, end_row: u16) -> Self {
+        Self { start_row, end_row }
+    }
+}
+
+impl DcsCommand for SetPartialRows {
+    fn instruction(&self) -> u8 {
+        0x30
+    }
+
+    fn fill_params_buf(&self, buffer: &mut [u8]) -> usize {
+        buffer[0..2].copy_from_slice(&self.start_row.to_be_bytes());
+        buffer[2..4].copy_from_slice(&self.end_row.to_be_bytes());
+
+        4
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ptlar_fills_data_properly() {
+        let ptlar = SetPartialRows::new(16, 300);
+
+        let mut buffer = [0u8; 4];
+        assert_eq!(ptlar.instruction(), 0x30);
+        assert_eq!(ptlar.fill_params_buf(&mut buffer), 4);
+        assert_eq!(buffer, [0, 0x10, 0x1, 0x2C]);
+    }
+}
@@ -5,9 +5,9 @@ use super::DcsCommand;
 /// Set Scroll Area
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SetScrollArea {
-    tfa: u16,
-    vsa: u16,
-    bfa: u16,
+    pub(crate) tfa: u16,
+    pub(crate) vsa: u16,
+    pub(crate) bfa: u16,
 }
 
 impl SetScrollArea {
@@ -0,0 +1,112 @@
+//! Module for the WRDISBV, WRCTRLD and WRCABC brightness instruction constructors
+
+use crate::options::{BrightnessControl, CabcMode};
+
+use super::DcsCommand;
+
+/// Write Display Brightness
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WriteDisplayBrightness(u8);
+
+impl WriteDisplayBrightness {
+    /// Creates a new Write Display Brightness command.
+    pub const fn new(brightness: u8) -> Self {
+        Self(brightness)
+    }
+}
+
+impl DcsCommand for WriteDisplayBrightness {
+    fn instruction(&self) -> u8 {
+        0x51
+    }
+
+    fn fill_params_buf(&self, buffer: &mut [u8]) -> usize {
+        buffer[0] = self.0;
+        1
+    }
+}
+
+/// Write CTRL Display
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WriteCtrlDisplay(BrightnessControl);
+
+impl WriteCtrlDisplay {
+    /// Creates a new Write CTRL Display command.
+    pub const fn new(control: BrightnessControl) -> Self {
+        Self(control)
+    }
+}
+
+impl DcsCommand for WriteCtrlDisplay {
+    fn instruction(&self) -> u8 {
+        0x53
+    }
+
+    fn fill_params_buf(&self, buffer: &mut [u8]) -> usize {
+        let BrightnessControl {
+            brightness_control,
+            dimming,
+            backlight,
+        } = self.0;
+
+        buffer[0] =
+            u8::from(brightness_control) << 5 | u8::from(dimming) << 3 | u8::from(backlight) << 2;
+        1
+    }
+}
+
+/// Write Content Adaptive Brightness Control
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WriteCabc(CabcMode);
+
+impl WriteCabc {
+    /// Creates a new Write Content Adaptive Brightness Control command.
+    pub const fn new(mode: CabcMode) -> Self {
+        Self(mode)
+    }
+}
+
+impl DcsCommand for WriteCabc {
+    fn instruction(&self) -> u8 {
+        0x55
+    }
+
+    fn fill_params_buf(&self, buffer: &mut [u8]) -> usize {
+        buffer[0] = match self.0 {
+            CabcMode::Off => 0b00,
+            CabcMode::UserInterface => 0b01,
+            CabcMode::StillPicture => 0b10,
+            CabcMode::MovingImage => 0b11,
+        };
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrctrld_fills_bits_properly() {
+        let wrctrld = WriteCtrlDisplay::new(BrightnessControl {
+            brightness_control: true,
+            dimming: true,
+            backlight: true,
+        });
+
+        let mut buffer = [0u8; 1];
+        assert_eq!(wrctrld.instruction(), 0x53);
+        assert_eq!(wrctrld.fill_params_buf(&mut buffer), 1);
+        assert_eq!(buffer, [0x2C]);
+    }
+
+    #[test]
+    fn wrcabc_fills_mode_properly() {
+        let wrcabc = WriteCabc::new(CabcMode::StillPicture);
+
+        let mut buffer = [0u8; 1];
+        assert_eq!(wrcabc.instruction(), 0x55);
+        assert_eq!(wrcabc.fill_params_buf(&mut buffer), 1);
+        assert_eq!(buffer, [0x02]);
+    }
+}
@@ -7,17 +7,21 @@ use embedded_graphics_core::{
 };
 use embedded_hal::digital::OutputPin;
 
+use crate::backlight::Backlight;
 use crate::dcs::InterfaceExt;
+use crate::options::{IndexBits, VisibleArea};
 use crate::{dcs::BitsPerPixel, interface::Interface};
 use crate::{dcs::WriteMemoryStart, models::Model};
 use crate::{interface::InterfacePixelFormat, Display};
 
-impl<DI, M, RST> DrawTarget for Display<DI, M, RST>
+impl<DI, M, RST, BL, PWR> DrawTarget for Display<DI, M, RST, BL, PWR>
 where
     DI: Interface,
     M: Model,
     M::ColorFormat: InterfacePixelFormat<DI::Word>,
     RST: OutputPin,
+    BL: Backlight,
+    PWR: OutputPin,
 {
     type Error = DI::Error;
     type Color = M::ColorFormat;
@@ -28,6 +32,10 @@ where
         I: IntoIterator<Item = Pixel<Self::Color>>,
     {
         for pixel in pixels {
+            if !self.is_visible(pixel.0) {
+                continue;
+            }
+
             let x = pixel.0.x as u16;
             let y = pixel.0.y as u16;
 
@@ -68,7 +76,33 @@ where
 
         let mut colors = colors.into_iter();
 
-        if &intersection == area {
+        if self.options.visible_area != VisibleArea::Full {
+            // Send each row separately, trimmed to the visible columns
+            for y in area.rows() {
+                let mut row = colors.by_ref().take(area.size.width as usize);
+
+                if let Some((start, end)) = self.visible_span(y, sx.into(), ex.into()) {
+                    let skip = (i32::from(start) - area.top_left.x) as usize;
+                    let count = usize::from(end - start) + 1;
+                    self.set_pixels(
+                        start,
+                        y as u16,
+                        end,
+                        y as u16,
+                        row.by_ref().skip(skip).take(count),
+                    )?;
+                }
+
+                // Rows which are (partially) outside the visible area aren't completely consumed
+                row.for_each(drop);
+
+                if y >= bottom_right.y {
+                    break;
+                }
+            }
+
+            Ok(())
+        } else if &intersection == area {
             // Draw the original iterator if no edge overlaps the framebuffer
             self.set_pixels(sx, sy, ex, ey, take_u32(colors, count))
         } else {
@@ -104,25 +138,88 @@ where
             return Ok(());
         };
 
-        let count = area.size.width * area.size.height;
-
         let sx = area.top_left.x as u16;
         let sy = area.top_left.y as u16;
         let ex = bottom_right.x as u16;
         let ey = bottom_right.y as u16;
 
+        if self.options.visible_area == VisibleArea::Full {
+            return self.fill_window(sx, sy, ex, ey, color);
+        }
+
+        // Consecutive rows with the same visible columns are combined into a single window
+        let mut block: Option<(u16, u16, u16, u16)> = None;
+        for y in sy..=ey {
+            let span = self.visible_span(y.into(), sx.into(), ex.into());
+
+            match (block, span) {
+                (Some((start, end, top, _)), Some(span)) if span == (start, end) => {
+                    block = Some((start, end, top, y));
+                }
+                _ => {
+                    if let Some((start, end, top, bottom)) = block {
+                        self.fill_window(start, top, end, bottom, color)?;
+                    }
+                    block = span.map(|(start, end)| (start, end, y, y));
+                }
+            }
+        }
+
+        if let Some((start, end, top, bottom)) = block {
+            self.fill_window(start, top, end, bottom, color)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<DI, M, RST, BL, PWR> Display<DI, M, RST, BL, PWR>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+    PWR: OutputPin,
+{
+    /// Returns the visible part of the columns `start..=end` in row `y`.
+    pub(crate) fn visible_span(&self, y: i32, start: i32, end: i32) -> Option<(u16, u16)> {
+        let columns = self.visible_columns(y)?;
+        let start = start.max(*columns.start());
+        let end = end.min(*columns.end());
+
+        (start <= end).then_some((start as u16, end as u16))
+    }
+
+    /// Fills a window with a single color.
+    fn fill_window(
+        &mut self,
+        sx: u16,
+        sy: u16,
+        ex: u16,
+        ey: u16,
+        color: M::ColorFormat,
+    ) -> Result<(), DI::Error> {
+        if !self.state.powered {
+            return Ok(());
+        }
+
+        let count = u32::from(ex - sx + 1) * u32::from(ey - sy + 1);
+
         self.set_address_window(sx, sy, ex, ey)?;
         self.di.write_command(WriteMemoryStart)?;
         M::ColorFormat::send_repeated_pixel(&mut self.di, color, count)
     }
 }
 
-impl<DI, MODEL, RST> OriginDimensions for Display<DI, MODEL, RST>
+impl<DI, MODEL, RST, BL, PWR> OriginDimensions for Display<DI, MODEL, RST, BL, PWR>
 where
     DI: Interface,
     MODEL: Model,
     MODEL::ColorFormat: InterfacePixelFormat<DI::Word>,
     RST: OutputPin,
+    BL: Backlight,
+    PWR: OutputPin,
 {
     fn size(&self) -> Size {
         let ds = self.options.display_size();
@@ -148,6 +245,31 @@ impl BitsPerPixel {
     }
 }
 
+/// Returns an iterator over the colors of a packed indexed color image.
+///
+/// Each row starts at a byte boundary and pixels are packed MSB first.
+/// Indices which are outside the palette are mapped to `fallback`.
+pub(crate) fn indexed_colors<'a, C: Copy>(
+    data: &'a [u8],
+    width: u32,
+    bits: IndexBits,
+    palette: &'a [C],
+    fallback: C,
+) -> impl Iterator<Item = C> + 'a {
+    let bits = u32::from(bits.bits());
+    let mask = ((1u16 << bits) - 1) as u8;
+    let stride = (width * bits).div_ceil(8) as usize;
+
+    data.chunks_exact(stride.max(1)).flat_map(move |row| {
+        (0..width).map(move |x| {
+            let bit = x * bits;
+            let shift = 8 - bits - bit % 8;
+            let index = (row[(bit / 8) as usize] >> shift) & mask;
+            palette.get(usize::from(index)).copied().unwrap_or(fallback)
+        })
+    })
+}
+
 /// An iterator that alternately takes and skips elements of another iterator.
 struct TakeSkip<I> {
     iter: I,
@@ -215,7 +337,8 @@ mod test {
     use crate::dcs::BitsPerPixel;
     use embedded_graphics_core::pixelcolor::*;
 
-    use super::TakeSkip;
+    use super::{indexed_colors, TakeSkip};
+    use crate::options::IndexBits;
 
     #[test]
     fn bpp_from_rgb_color_works() {
@@ -261,4 +384,30 @@ mod test {
         let mut iter = TakeSkip::new(0..11, 0, 2);
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn indexed_colors_unpacks_rows() {
+        let palette = [10, 11, 12, 13];
+
+        // 3 pixels per row, rows are padded to the next byte boundary
+        let data = [0b00_01_10_00, 0b11_11_00_00];
+        let colors: [u8; 6] = core::array::from_fn({
+            let mut iter = indexed_colors(&data, 3, IndexBits::Two, &palette, 0);
+            move |_| iter.next().unwrap()
+        });
+        assert_eq!(colors, [10, 11, 12, 13, 13, 10]);
+
+        let data = [0b1010_0000];
+        let mut iter = indexed_colors(&data, 3, IndexBits::One, &palette, 0);
+        assert!(iter.by_ref().take(3).eq([11, 10, 11]));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn indexed_colors_uses_fallback() {
+        let palette = [1, 2];
+        let data = [0x01, 0x05];
+        let iter = indexed_colors(&data, 1, IndexBits::Eight, &palette, 0);
+        assert!(iter.eq([2, 0]));
+    }
 }
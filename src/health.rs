@@ -0,0 +1,205 @@
+use embedded_hal::{delay::DelayNs, digital::OutputPin};
+
+use crate::{
+    backlight::Backlight,
+    dcs::{
+        BitsPerPixel, DcsCommand, DisplayStatus, PixelFormat, ReadDisplayStatus, ReadInterfaceExt,
+        ReadMadctl, ReadPixelFormat,
+    },
+    interface::{InterfacePixelFormat, ReadInterface},
+    models::Model,
+    Display, InitError,
+};
+
+/// MADCTL bits which are returned by all supported controllers.
+const MADCTL_MASK: u8 = 0b1111_1100;
+
+/// Result of a display health check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Diagnosis {
+    /// The controller state matches the cached state.
+    Healthy,
+    /// The controller is in sleep mode with the display output turned off,
+    /// while it's expected to be awake, which indicates that the controller
+    /// was reset.
+    Reset,
+    /// The controller state doesn't match the cached state.
+    ///
+    /// Contains the values which were read from the controller.
+    Mismatch {
+        /// Response of the Read Display Status (0x09) command.
+        status: DisplayStatus,
+        /// Value of the Read Display MADCTL (0x0B) command.
+        madctl: u8,
+        /// Value of the Read Display Pixel Format (0x0C) command.
+        pixel_format: u8,
+    },
+    /// The display was powered off using
+    /// [`Display::power_off`](crate::Display::power_off) and the controller
+    /// wasn't checked.
+    PoweredOff,
+}
+
+impl<DI, M, RST, BL, PWR> Display<DI, M, RST, BL, PWR>
+where
+    DI: ReadInterface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+    PWR: OutputPin,
+{
+    /// Checks if the controller state matches the state cached by the driver.
+    ///
+    /// The display status, MADCTL and pixel format are read from the controller
+    /// and compared with the expected values. The sleep, display on, idle and
+    /// partial mode state is taken from the display status. This can be used to detect controllers
+    /// which were silently reset, e.g. by ESD events or glitches on the cable.
+    ///
+    /// The check is only reliable if the controller state isn't changed by
+    /// sending raw commands using [`dcs`](Self::dcs).
+    ///
+    /// Returns [`Diagnosis::PoweredOff`] without reading the controller state
+    /// if the display is powered off.
+    pub fn check_health(&mut self) -> Result<Diagnosis, DI::Error> {
+        if !self.state.powered {
+            return Ok(Diagnosis::PoweredOff);
+        }
+
+        let status = self.di.read_dcs_command(ReadDisplayStatus)?;
+        let madctl = self.di.read_dcs_command(ReadMadctl)?;
+        let pixel_format = self.di.read_dcs_command(ReadPixelFormat)?;
+
+        let mut expected_madctl = [0];
+        self.madctl.fill_params_buf(&mut expected_madctl);
+        let expected_pixel_format =
+            PixelFormat::with_all(BitsPerPixel::from_rgb_color::<M::ColorFormat>()).as_u8();
+
+        if !status.sleep_out && !status.display_on && !self.state.sleeping {
+            return Ok(Diagnosis::Reset);
+        }
+
+        if status.sleep_out != self.state.sleeping
+            && status.display_on == self.state.display_on
+            && status.idle == self.state.idle
+            && status.partial == self.state.partial
+            && madctl & MADCTL_MASK == expected_madctl[0] & MADCTL_MASK
+            && pixel_format == expected_pixel_format
+        {
+            Ok(Diagnosis::Healthy)
+        } else {
+            Ok(Diagnosis::Mismatch {
+                status,
+                madctl,
+                pixel_format,
+            })
+        }
+    }
+
+    /// Checks the controller state and recovers the display if it was reset.
+    ///
+    /// If [`check_health`](Self::check_health) returns [`Diagnosis::Reset`] the
+    /// display is reinitialized using [`reinit`](Self::reinit), which restores
+    /// the cached state, the content is redrawn by calling `redraw` and the
+    /// backlight is restored afterwards. Other diagnoses are returned without
+    /// changing the display state, because a sleeping or turned off display
+    /// must not be woken up. Returns the diagnosis of the health check.
+    pub fn check_health_and_recover<D, F>(
+        &mut self,
+        delay: &mut D,
+        redraw: F,
+    ) -> Result<Diagnosis, InitError<DI::Error, RST::Error, BL::Error, PWR::Error>>
+    where
+        D: DelayNs,
+        F: FnOnce(&mut Self) -> Result<(), DI::Error>,
+    {
+        let diagnosis = self.check_health().map_err(InitError::Interface)?;
+        if diagnosis != Diagnosis::Reset {
+            return Ok(diagnosis);
+        }
+
+        self.reinit(delay)?;
+        redraw(self).map_err(InitError::Interface)?;
+        self.set_backlight(self.backlight_level)
+            .map_err(InitError::Backlight)?;
+
+        Ok(diagnosis)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cell::RefCell;
+
+    use super::*;
+    use crate::{
+        _mock::{MockDelay, MockInterface, MockInterfaceState},
+        dcs::DcsReadCommand,
+        models::ILI9341Rgb565,
+        Builder,
+    };
+
+    /// Display status of an awake controller with the display output on.
+    const AWAKE_STATUS: [u8; 4] = [0x80, 0b0101_0011, 0b0000_0100, 0];
+    /// Display status of a controller after a reset.
+    const RESET_STATUS: [u8; 4] = [0, 0b0110_0001, 0, 0];
+
+    fn set_status(state: &RefCell<MockInterfaceState>, status: [u8; 4]) {
+        let read_values = &mut state.borrow_mut().read_values;
+        read_values[usize::from(ReadDisplayStatus.instruction())] = status;
+        read_values[usize::from(ReadMadctl.instruction())][0] = 0b0000_1000;
+        read_values[usize::from(ReadPixelFormat.instruction())][0] = 0b0101_0101;
+    }
+
+    #[test]
+    fn detects_reset() {
+        let state = RefCell::new(MockInterfaceState::new());
+        set_status(&state, AWAKE_STATUS);
+        let mut display = Builder::new(ILI9341Rgb565, MockInterface(&state))
+            .color_order(crate::options::ColorOrder::Bgr)
+            .init(&mut MockDelay)
+            .unwrap();
+        assert_eq!(display.check_health(), Ok(Diagnosis::Healthy));
+
+        set_status(&state, RESET_STATUS);
+        let mut redrawn = false;
+        let diagnosis = display
+            .check_health_and_recover(&mut MockDelay, |_| {
+                redrawn = true;
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(diagnosis, Diagnosis::Reset);
+        assert!(redrawn);
+
+        set_status(&state, AWAKE_STATUS);
+        assert_eq!(display.check_health(), Ok(Diagnosis::Healthy));
+        display.set_idle_mode(true).unwrap();
+        assert!(matches!(
+            display.check_health(),
+            Ok(Diagnosis::Mismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn sleeping_display_isnt_recovered() {
+        let state = RefCell::new(MockInterfaceState::new());
+        set_status(&state, AWAKE_STATUS);
+        let mut display = Builder::new(ILI9341Rgb565, MockInterface(&state))
+            .color_order(crate::options::ColorOrder::Bgr)
+            .init(&mut MockDelay)
+            .unwrap();
+        display.sleep(&mut MockDelay).unwrap();
+
+        set_status(&state, RESET_STATUS);
+        let diagnosis = display
+            .check_health_and_recover(&mut MockDelay, |_| panic!("display was redrawn"))
+            .unwrap();
+        assert!(matches!(diagnosis, Diagnosis::Mismatch { .. }));
+        assert!(display.is_sleeping());
+        assert!(matches!(
+            display.check_health(),
+            Ok(Diagnosis::Mismatch { .. })
+        ));
+    }
+}
@@ -0,0 +1,230 @@
+use embedded_graphics_core::pixelcolor::RgbColor;
+use embedded_hal::digital::OutputPin;
+
+use crate::{
+    adapters::idle_color_bits,
+    backlight::Backlight,
+    dcs::{self, BitsPerPixel, InterfaceExt, PixelFormat},
+    interface::{Interface, InterfacePixelFormat},
+    models::Model,
+    Display, StateError,
+};
+
+/// Error returned by [`Display::set_pixels_3bpp`].
+#[derive(Debug)]
+pub enum Pixels3bppError<DI> {
+    /// Error caused by the display interface.
+    Interface(DI),
+    /// The display model doesn't support the 3 bits per pixel format.
+    ///
+    /// See [`Model::PIXEL_FORMAT_3BPP`].
+    Unsupported,
+}
+
+impl<DI, M, RST, BL, PWR> Display<DI, M, RST, BL, PWR>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+    PWR: OutputPin,
+{
+    /// Enters or exits idle mode.
+    ///
+    /// In idle mode the display only shows 8 colors, by using only the most
+    /// significant bit of every color channel, which reduces the power
+    /// consumption of the display. The content of the framebuffer isn't changed
+    /// and all colors are shown normally again after idle mode was exited.
+    ///
+    /// Use the [`IdleColorQuantizer`](crate::adapters::IdleColorQuantizer)
+    /// adapter to draw content which is intended to be shown in idle mode.
+    ///
+    /// Returns [`StateError::PoweredOff`] if the display is powered off and
+    /// [`StateError::Sleeping`] if the display is sleeping.
+    pub fn set_idle_mode(&mut self, idle: bool) -> Result<(), StateError<DI::Error>> {
+        self.check_awake()?;
+        if idle {
+            self.di
+                .write_command(dcs::EnterIdleMode)
+                .map_err(StateError::Interface)?;
+        } else {
+            self.di
+                .write_command(dcs::ExitIdleMode)
+                .map_err(StateError::Interface)?;
+        }
+        self.state.idle = idle;
+
+        Ok(())
+    }
+}
+
+impl<DI, M, RST, BL, PWR> Display<DI, M, RST, BL, PWR>
+where
+    DI: Interface<Word = u8>,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<u8> + RgbColor,
+    RST: OutputPin,
+    BL: Backlight,
+    PWR: OutputPin,
+{
+    /// Sets pixel colors in a rectangular region using 3 bits per pixel.
+    ///
+    /// This method works like [`set_pixels`](Self::set_pixels), but quantizes
+    /// the colors to the 8 idle mode colors and sends two pixels per byte,
+    /// which reduces the amount of transferred data to a quarter of the data
+    /// required for RGB565 pixels. The pixel format of the display is set to
+    /// [`BitsPerPixel::Three`] for the transfer and restored afterwards.
+    ///
+    /// Not all display controllers support the 3 bit pixel format on the
+    /// interface used to connect the display. Returns
+    /// [`Pixels3bppError::Unsupported`] if the model doesn't support the
+    /// format, see [`Model::PIXEL_FORMAT_3BPP`]. The pixel format is restored
+    /// even if sending the pixels fails. Does nothing while the display is
+    /// powered off, see [`power_off`](Self::power_off).
+    ///
+    /// <div class="warning">
+    ///
+    /// The end values of the X and Y coordinate ranges are inclusive, and no
+    /// bounds checking is performed on these values.
+    ///
+    /// </div>
+    pub fn set_pixels_3bpp<T>(
+        &mut self,
+        sx: u16,
+        sy: u16,
+        ex: u16,
+        ey: u16,
+        colors: T,
+    ) -> Result<(), Pixels3bppError<DI::Error>>
+    where
+        T: IntoIterator<Item = M::ColorFormat>,
+    {
+        if !M::PIXEL_FORMAT_3BPP {
+            return Err(Pixels3bppError::Unsupported);
+        }
+        if !self.state.powered {
+            return Ok(());
+        }
+
+        self.set_address_window(sx, sy, ex, ey)
+            .map_err(Pixels3bppError::Interface)?;
+
+        let result = self.send_pixels_3bpp(colors);
+
+        // The pixel format is restored even if the transfer failed, but the
+        // first error is returned
+        let pixel_format = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<M::ColorFormat>());
+        let restore = self
+            .di
+            .write_command(dcs::SetPixelFormat::new(pixel_format));
+
+        result.and(restore).map_err(Pixels3bppError::Interface)
+    }
+
+    /// Sets the 3 bit pixel format and sends the packed pixels.
+    fn send_pixels_3bpp<T>(&mut self, colors: T) -> Result<(), DI::Error>
+    where
+        T: IntoIterator<Item = M::ColorFormat>,
+    {
+        self.di
+            .write_command(dcs::SetPixelFormat::new(PixelFormat::with_all(
+                BitsPerPixel::Three,
+            )))?;
+        self.di.write_command(dcs::WriteMemoryStart)?;
+        self.di.send_pixels(pack_3bpp(colors.into_iter()))
+    }
+}
+
+/// Packs two pixels into each byte, with the first pixel in bits 5 to 3.
+///
+/// If the number of colors is odd the last byte is padded with a black pixel.
+fn pack_3bpp<C: RgbColor>(colors: impl Iterator<Item = C>) -> impl Iterator<Item = [u8; 1]> {
+    let mut colors = colors.map(idle_color_bits);
+
+    core::iter::from_fn(move || {
+        let first = colors.next()?;
+        let second = colors.next().unwrap_or(0);
+
+        Some([first << 3 | second])
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cell::RefCell;
+
+    use embedded_graphics_core::pixelcolor::Rgb565;
+    use embedded_hal::delay::DelayNs;
+
+    use super::*;
+    use crate::{
+        _mock::{
+            MockDelay, MockDisplayInterface, MockInterface, MockInterfaceError, MockInterfaceState,
+        },
+        dcs::SetAddressMode,
+        models::ILI9341Rgb565,
+        options::ModelOptions,
+        Builder,
+    };
+
+    /// ILI9341 which supports the 3 bit pixel format.
+    struct Model3bpp;
+
+    impl Model for Model3bpp {
+        type ColorFormat = Rgb565;
+        const FRAMEBUFFER_SIZE: (u16, u16) = ILI9341Rgb565::FRAMEBUFFER_SIZE;
+        const PIXEL_FORMAT_3BPP: bool = true;
+
+        fn init<DELAY, DI>(
+            &mut self,
+            di: &mut DI,
+            delay: &mut DELAY,
+            options: &ModelOptions,
+        ) -> Result<SetAddressMode, DI::Error>
+        where
+            DELAY: DelayNs,
+            DI: Interface,
+        {
+            ILI9341Rgb565.init(di, delay, options)
+        }
+    }
+
+    #[test]
+    fn requires_model_support() {
+        let mut display = Builder::new(ILI9341Rgb565, MockDisplayInterface)
+            .init(&mut MockDelay)
+            .unwrap();
+
+        assert!(matches!(
+            display.set_pixels_3bpp(0, 0, 1, 0, [Rgb565::RED; 2]),
+            Err(Pixels3bppError::Unsupported)
+        ));
+    }
+
+    #[test]
+    fn restores_pixel_format_on_error() {
+        let state = RefCell::new(MockInterfaceState::new());
+        let mut display = Builder::new(Model3bpp, MockInterface(&state))
+            .init(&mut MockDelay)
+            .unwrap();
+        assert_eq!(state.borrow().params[0x3A][0], 0x55);
+
+        state.borrow_mut().fail_pixels = true;
+        assert!(matches!(
+            display.set_pixels_3bpp(0, 0, 1, 0, [Rgb565::RED; 2]),
+            Err(Pixels3bppError::Interface(MockInterfaceError))
+        ));
+        assert_eq!(state.borrow().params[0x3A][0], 0x55);
+    }
+
+    #[test]
+    fn pixels_are_packed() {
+        let colors = [Rgb565::RED, Rgb565::BLUE, Rgb565::WHITE];
+        let mut bytes = pack_3bpp(colors.into_iter());
+
+        assert_eq!(bytes.next(), Some([0b100_001]));
+        assert_eq!(bytes.next(), Some([0b111_000]));
+        assert_eq!(bytes.next(), None);
+    }
+}
@@ -0,0 +1,318 @@
+//! Streaming image decoders.
+//!
+//! The decoders in this module send the decoded pixels directly to the display,
+//! without buffering the image in RAM. Images can be read from byte slices or,
+//! if the `embedded-io` feature is enabled, from any `embedded_io::Read`
+//! implementation. Images stored in external NOR flash can be drawn using any
+//! `embedded_storage::nor_flash::ReadNorFlash` implementation if the
+//! `embedded-storage` feature is enabled.
+
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{Point, Size},
+    pixelcolor::Rgb888,
+    primitives::Rectangle,
+};
+use embedded_hal::digital::OutputPin;
+
+use crate::{
+    backlight::Backlight,
+    interface::{Interface, InterfacePixelFormat},
+    models::Model,
+    Display,
+};
+
+mod bmp;
+#[cfg(feature = "embedded-storage")]
+mod flash;
+#[cfg(feature = "embedded-storage")]
+pub use flash::*;
+mod qoi;
+pub use qoi::*;
+mod tga;
+
+/// Error returned by the image drawing methods.
+#[derive(Debug)]
+pub enum ImageError<DI, R> {
+    /// Error caused by the display interface.
+    Interface(DI),
+    /// Error caused by the image source.
+    Read(R),
+    /// The image data is invalid or uses an unsupported format.
+    InvalidFormat,
+    /// The image data ended before all pixels were read.
+    UnexpectedEof,
+}
+
+/// Error returned by image decoders.
+#[derive(Debug)]
+pub(crate) enum DecodeError<R> {
+    // Slices are the only source without `embedded-io` or `embedded-storage`, which can't fail
+    #[cfg_attr(
+        not(any(feature = "embedded-io", feature = "embedded-storage")),
+        allow(dead_code)
+    )]
+    Read(R),
+    InvalidFormat,
+    UnexpectedEof,
+}
+
+impl<DI, R> From<DecodeError<R>> for ImageError<DI, R> {
+    fn from(error: DecodeError<R>) -> Self {
+        match error {
+            DecodeError::Read(e) => Self::Read(e),
+            DecodeError::InvalidFormat => Self::InvalidFormat,
+            DecodeError::UnexpectedEof => Self::UnexpectedEof,
+        }
+    }
+}
+
+/// A source of image bytes.
+pub(crate) trait ByteReader {
+    type Error;
+
+    /// Fills the whole buffer or returns an error.
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), DecodeError<Self::Error>>;
+
+    fn read_u8(&mut self) -> Result<u8, DecodeError<Self::Error>> {
+        let mut byte = [0];
+        self.read_exact(&mut byte)?;
+        Ok(byte[0])
+    }
+
+    /// Discards the next `count` bytes.
+    fn skip(&mut self, mut count: usize) -> Result<(), DecodeError<Self::Error>> {
+        let mut buf = [0; 16];
+        while count > 0 {
+            let n = count.min(buf.len());
+            self.read_exact(&mut buf[..n])?;
+            count -= n;
+        }
+        Ok(())
+    }
+}
+
+/// Byte reader for in memory images.
+pub(crate) struct SliceReader<'a>(pub &'a [u8]);
+
+impl ByteReader for SliceReader<'_> {
+    type Error = core::convert::Infallible;
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), DecodeError<Self::Error>> {
+        if self.0.len() < buf.len() {
+            return Err(DecodeError::UnexpectedEof);
+        }
+
+        let (head, tail) = self.0.split_at(buf.len());
+        buf.copy_from_slice(head);
+        self.0 = tail;
+
+        Ok(())
+    }
+}
+
+/// Byte reader for `embedded_io::Read` implementations.
+///
+/// Small reads are served from an internal buffer to avoid calling the
+/// underlying reader for every single byte.
+#[cfg(feature = "embedded-io")]
+pub(crate) struct IoReader<'r, R> {
+    reader: &'r mut R,
+    buffer: [u8; 64],
+    start: usize,
+    end: usize,
+}
+
+#[cfg(feature = "embedded-io")]
+impl<'r, R: embedded_io::Read> IoReader<'r, R> {
+    pub fn new(reader: &'r mut R) -> Self {
+        Self {
+            reader,
+            buffer: [0; 64],
+            start: 0,
+            end: 0,
+        }
+    }
+}
+
+#[cfg(feature = "embedded-io")]
+impl<R: embedded_io::Read> ByteReader for IoReader<'_, R> {
+    type Error = R::Error;
+
+    fn read_exact(&mut self, mut buf: &mut [u8]) -> Result<(), DecodeError<Self::Error>> {
+        while !buf.is_empty() {
+            if self.start == self.end {
+                self.start = 0;
+                self.end = self
+                    .reader
+                    .read(&mut self.buffer)
+                    .map_err(DecodeError::Read)?;
+                if self.end == 0 {
+                    return Err(DecodeError::UnexpectedEof);
+                }
+            }
+
+            let n = buf.len().min(self.end - self.start);
+            buf[..n].copy_from_slice(&self.buffer[self.start..self.start + n]);
+            self.start += n;
+            buf = &mut buf[n..];
+        }
+
+        Ok(())
+    }
+}
+
+/// Iterator adapter which stops at the first decoder error and stores it.
+///
+/// This is used to pass fallible decoders to the infallible pixel iterators
+/// used by [`Display`](crate::Display).
+pub(crate) struct StopOnError<'e, I, E> {
+    iter: I,
+    error: &'e mut Option<E>,
+}
+
+impl<'e, I, E> StopOnError<'e, I, E> {
+    pub fn new(iter: I, error: &'e mut Option<E>) -> Self {
+        Self { iter, error }
+    }
+}
+
+impl<I, T, E> Iterator for StopOnError<'_, I, E>
+where
+    I: Iterator<Item = Result<T, E>>,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.error.is_some() {
+            return None;
+        }
+
+        match self.iter.next()? {
+            Ok(item) => Some(item),
+            Err(e) => {
+                *self.error = Some(e);
+                None
+            }
+        }
+    }
+}
+
+/// Pixel layouts used by uncompressed image formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PixelLayout {
+    /// 8 bit grayscale.
+    Gray8,
+    /// 16 bit little endian `0bXRRRRRGG_GGGBBBBB`.
+    Rgb555,
+    /// 16 bit little endian `0bRRRRRGGG_GGGBBBBB`.
+    Rgb565,
+    /// 24 bit, stored as `[b, g, r]`.
+    Bgr888,
+    /// 32 bit, stored as `[b, g, r, a]`.
+    Bgra8888,
+}
+
+impl PixelLayout {
+    /// Returns the number of bytes per pixel.
+    pub fn bytes(self) -> usize {
+        match self {
+            Self::Gray8 => 1,
+            Self::Rgb555 | Self::Rgb565 => 2,
+            Self::Bgr888 => 3,
+            Self::Bgra8888 => 4,
+        }
+    }
+
+    /// Reads a single pixel.
+    pub fn read<B: ByteReader>(self, reader: &mut B) -> Result<Rgb888, DecodeError<B::Error>> {
+        let mut bytes = [0; 4];
+        reader.read_exact(&mut bytes[..self.bytes()])?;
+
+        let expand5 = |v: u16| ((v << 3) | (v >> 2)) as u8;
+        let expand6 = |v: u16| ((v << 2) | (v >> 4)) as u8;
+
+        Ok(match self {
+            Self::Gray8 => Rgb888::new(bytes[0], bytes[0], bytes[0]),
+            Self::Rgb555 => {
+                let v = u16::from_le_bytes([bytes[0], bytes[1]]);
+                Rgb888::new(
+                    expand5((v >> 10) & 0x1F),
+                    expand5((v >> 5) & 0x1F),
+                    expand5(v & 0x1F),
+                )
+            }
+            Self::Rgb565 => {
+                let v = u16::from_le_bytes([bytes[0], bytes[1]]);
+                Rgb888::new(
+                    expand5((v >> 11) & 0x1F),
+                    expand6((v >> 5) & 0x3F),
+                    expand5(v & 0x1F),
+                )
+            }
+            Self::Bgr888 | Self::Bgra8888 => Rgb888::new(bytes[2], bytes[1], bytes[0]),
+        })
+    }
+}
+
+/// Row order of decoded images.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RowOrder {
+    TopDown,
+    BottomUp,
+}
+
+impl<DI, M, RST, BL, PWR> Display<DI, M, RST, BL, PWR>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+    PWR: OutputPin,
+{
+    /// Draws the pixels returned by an image decoder.
+    ///
+    /// Top down images are sent using a single address window. Bottom up images
+    /// are sent using one address window per row, which avoids buffering rows.
+    pub(crate) fn draw_decoded<I, C, E>(
+        &mut self,
+        position: Point,
+        size: Size,
+        row_order: RowOrder,
+        pixels: I,
+    ) -> Result<(), ImageError<DI::Error, E>>
+    where
+        I: Iterator<Item = Result<C, DecodeError<E>>>,
+        M::ColorFormat: From<C>,
+    {
+        let mut error = None;
+        let mut colors = StopOnError::new(pixels, &mut error).map(M::ColorFormat::from);
+
+        match row_order {
+            RowOrder::TopDown => {
+                let area = Rectangle::new(position, size);
+                self.fill_contiguous(&area, colors)
+                    .map_err(ImageError::Interface)?;
+            }
+            RowOrder::BottomUp => {
+                for y in (0..size.height as i32).rev() {
+                    let area =
+                        Rectangle::new(position + Point::new(0, y), Size::new(size.width, 1));
+
+                    // Rows which are (partially) outside the display aren't
+                    // completely consumed by `fill_contiguous`
+                    let mut row = colors.by_ref().take(size.width as usize);
+                    self.fill_contiguous(&area, &mut row)
+                        .map_err(ImageError::Interface)?;
+                    row.for_each(drop);
+                }
+            }
+        }
+
+        match error {
+            Some(e) => Err(e.into()),
+            None => Ok(()),
+        }
+    }
+}
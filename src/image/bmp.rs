@@ -0,0 +1,263 @@
+use embedded_graphics_core::{
+    geometry::{Point, Size},
+    pixelcolor::Rgb888,
+};
+use embedded_hal::digital::OutputPin;
+
+use super::{ByteReader, DecodeError, ImageError, PixelLayout, RowOrder, SliceReader};
+use crate::{
+    backlight::Backlight,
+    interface::{Interface, InterfacePixelFormat},
+    models::Model,
+    Display,
+};
+
+/// Size of the file header and the `BITMAPINFOHEADER`.
+const HEADER_SIZE: usize = 14 + 40;
+
+const BI_RGB: u32 = 0;
+const BI_BITFIELDS: u32 = 3;
+
+/// Parsed BMP header.
+struct BmpHeader {
+    size: Size,
+    row_order: RowOrder,
+    layout: PixelLayout,
+}
+
+impl BmpHeader {
+    /// Reads the header and skips to the start of the pixel data.
+    fn read<B: ByteReader>(reader: &mut B) -> Result<Self, DecodeError<B::Error>> {
+        let mut bytes = [0; HEADER_SIZE];
+        reader.read_exact(&mut bytes)?;
+
+        let u16_at = |i: usize| u16::from_le_bytes([bytes[i], bytes[i + 1]]);
+        let u32_at =
+            |i: usize| u32::from_le_bytes([bytes[i], bytes[i + 1], bytes[i + 2], bytes[i + 3]]);
+
+        let data_offset = u32_at(10) as usize;
+        let dib_size = u32_at(14);
+        let width = u32_at(18) as i32;
+        let height = u32_at(22) as i32;
+        let bpp = u16_at(28);
+        let compression = u32_at(30);
+
+        if bytes[0..2] != *b"BM" || dib_size < 40 || width <= 0 || height == 0 {
+            return Err(DecodeError::InvalidFormat);
+        }
+
+        // The color masks directly follow the `BITMAPINFOHEADER` or are part
+        // of the V4 and V5 headers, which results in the same offset.
+        let mut consumed = HEADER_SIZE;
+        let masks = if compression == BI_BITFIELDS {
+            let mut masks = [0; 12];
+            reader.read_exact(&mut masks)?;
+            consumed += masks.len();
+
+            let mask_at =
+                |i: usize| u32::from_le_bytes([masks[i], masks[i + 1], masks[i + 2], masks[i + 3]]);
+            Some((mask_at(0), mask_at(4), mask_at(8)))
+        } else {
+            None
+        };
+
+        let layout = match (compression, bpp, masks) {
+            (BI_RGB, 16, _) => PixelLayout::Rgb555,
+            (BI_RGB, 24, _) => PixelLayout::Bgr888,
+            (BI_RGB, 32, _) => PixelLayout::Bgra8888,
+            (BI_BITFIELDS, 16, Some((0x7C00, 0x03E0, 0x001F))) => PixelLayout::Rgb555,
+            (BI_BITFIELDS, 16, Some((0xF800, 0x07E0, 0x001F))) => PixelLayout::Rgb565,
+            (BI_BITFIELDS, 32, Some((0x00FF_0000, 0x0000_FF00, 0x0000_00FF))) => {
+                PixelLayout::Bgra8888
+            }
+            _ => return Err(DecodeError::InvalidFormat),
+        };
+
+        let gap = data_offset
+            .checked_sub(consumed)
+            .ok_or(DecodeError::InvalidFormat)?;
+        reader.skip(gap)?;
+
+        // Positive heights are used for bottom up images
+        let row_order = if height > 0 {
+            RowOrder::BottomUp
+        } else {
+            RowOrder::TopDown
+        };
+
+        Ok(Self {
+            size: Size::new(width as u32, height.unsigned_abs()),
+            row_order,
+            layout,
+        })
+    }
+}
+
+/// Streaming decoder for uncompressed BMP images.
+struct BmpDecoder<R> {
+    reader: R,
+    layout: PixelLayout,
+    width: u32,
+    padding: usize,
+    x: u32,
+    remaining: u32,
+}
+
+impl<R: ByteReader> BmpDecoder<R> {
+    fn new(reader: R, header: &BmpHeader) -> Self {
+        let row_bytes = header.size.width as usize * header.layout.bytes();
+
+        Self {
+            reader,
+            layout: header.layout,
+            width: header.size.width,
+            // Rows are padded to a multiple of 4 bytes
+            padding: (4 - row_bytes % 4) % 4,
+            x: 0,
+            remaining: header.size.width.saturating_mul(header.size.height),
+        }
+    }
+
+    fn decode_pixel(&mut self) -> Result<Rgb888, DecodeError<R::Error>> {
+        let color = self.layout.read(&mut self.reader)?;
+
+        self.x += 1;
+        if self.x == self.width {
+            self.x = 0;
+            if self.remaining > 0 {
+                self.reader.skip(self.padding)?;
+            }
+        }
+
+        Ok(color)
+    }
+}
+
+impl<R: ByteReader> Iterator for BmpDecoder<R> {
+    type Item = Result<Rgb888, DecodeError<R::Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+
+        Some(self.decode_pixel())
+    }
+}
+
+impl<DI, M, RST, BL, PWR> Display<DI, M, RST, BL, PWR>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word> + From<Rgb888>,
+    RST: OutputPin,
+    BL: Backlight,
+    PWR: OutputPin,
+{
+    /// Draws a BMP image.
+    ///
+    /// Uncompressed 16, 24 and 32 bit per pixel images are supported. The top left corner of the
+    /// image is placed at `position` and parts of the image which are outside of the display are
+    /// clipped.
+    ///
+    /// Top down images are sent using a single address window. Bottom up images, which is the
+    /// default row order for BMP files, are sent using one address window per row to draw them
+    /// without buffering any rows.
+    pub fn draw_bmp(
+        &mut self,
+        position: Point,
+        data: &[u8],
+    ) -> Result<(), ImageError<DI::Error, core::convert::Infallible>> {
+        self.draw_bmp_from(position, SliceReader(data))
+    }
+
+    /// Draws a BMP image read from an [`embedded_io::Read`] source, like a file on an SD card.
+    ///
+    /// See [`draw_bmp`](Self::draw_bmp) for more information.
+    #[cfg(feature = "embedded-io")]
+    pub fn draw_bmp_stream<R: embedded_io::Read>(
+        &mut self,
+        position: Point,
+        reader: &mut R,
+    ) -> Result<(), ImageError<DI::Error, R::Error>> {
+        self.draw_bmp_from(position, super::IoReader::new(reader))
+    }
+
+    fn draw_bmp_from<B: ByteReader>(
+        &mut self,
+        position: Point,
+        mut reader: B,
+    ) -> Result<(), ImageError<DI::Error, B::Error>> {
+        let header = BmpHeader::read(&mut reader)?;
+        let decoder = BmpDecoder::new(reader, &header);
+        self.draw_decoded(position, header.size, header.row_order, decoder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Creates the headers for a 2x2 pixel, 24 bit BMP image.
+    fn header_24bit(height: i32) -> [u8; HEADER_SIZE] {
+        let mut header = [0; HEADER_SIZE];
+        header[0..2].copy_from_slice(b"BM");
+        header[10..14].copy_from_slice(&(HEADER_SIZE as u32).to_le_bytes());
+        header[14..18].copy_from_slice(&40u32.to_le_bytes());
+        header[18..22].copy_from_slice(&2i32.to_le_bytes());
+        header[22..26].copy_from_slice(&height.to_le_bytes());
+        header[26..28].copy_from_slice(&1u16.to_le_bytes());
+        header[28..30].copy_from_slice(&24u16.to_le_bytes());
+        header
+    }
+
+    #[test]
+    fn parses_bottom_up_header() {
+        let header = header_24bit(2);
+        let parsed = BmpHeader::read(&mut SliceReader(&header)).unwrap();
+
+        assert_eq!(parsed.size, Size::new(2, 2));
+        assert_eq!(parsed.row_order, RowOrder::BottomUp);
+        assert_eq!(parsed.layout, PixelLayout::Bgr888);
+
+        let header = header_24bit(-2);
+        let parsed = BmpHeader::read(&mut SliceReader(&header)).unwrap();
+        assert_eq!(parsed.row_order, RowOrder::TopDown);
+    }
+
+    #[test]
+    fn skips_row_padding() {
+        let mut data = [0; HEADER_SIZE + 16];
+        data[..HEADER_SIZE].copy_from_slice(&header_24bit(2));
+        data[HEADER_SIZE..].copy_from_slice(&[
+            3, 2, 1, 6, 5, 4, 0xAA, 0xAA, // row 1 with 2 bytes of padding
+            9, 8, 7, 12, 11, 10, 0xAA, 0xAA, // row 2 with 2 bytes of padding
+        ]);
+
+        let mut reader = SliceReader(&data);
+        let header = BmpHeader::read(&mut reader).unwrap();
+        let mut decoder = BmpDecoder::new(reader, &header);
+
+        for expected in [
+            Rgb888::new(1, 2, 3),
+            Rgb888::new(4, 5, 6),
+            Rgb888::new(7, 8, 9),
+            Rgb888::new(10, 11, 12),
+        ] {
+            assert_eq!(decoder.next().unwrap().unwrap(), expected);
+        }
+        assert!(decoder.next().is_none());
+    }
+
+    #[test]
+    fn rejects_unsupported_formats() {
+        let mut header = header_24bit(2);
+        header[28..30].copy_from_slice(&8u16.to_le_bytes());
+
+        assert!(matches!(
+            BmpHeader::read(&mut SliceReader(&header)),
+            Err(DecodeError::InvalidFormat)
+        ));
+    }
+}
@@ -0,0 +1,327 @@
+use embedded_graphics_core::{
+    pixelcolor::{raw::RawU16, Rgb565, Rgb666, RgbColor},
+    primitives::Rectangle,
+};
+use embedded_hal::digital::OutputPin;
+use embedded_storage::nor_flash::ReadNorFlash;
+
+use super::{ByteReader, DecodeError, ImageError, RowOrder};
+use crate::{
+    backlight::Backlight,
+    interface::{Interface, InterfacePixelFormat},
+    models::Model,
+    Display,
+};
+
+/// Number of bytes which are read from the flash at once.
+const CHUNK_SIZE: usize = 64;
+
+/// Format of images stored in NOR flash.
+///
+/// The images don't contain a header, the size of the image is defined by the
+/// area passed to [`Display::draw_flash_image`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlashImageFormat {
+    /// Raw Rgb565 pixels, stored as 2 big endian bytes per pixel.
+    Rgb565,
+    /// Raw Rgb666 pixels, stored as 3 bytes per pixel in `[r, g, b]` order with
+    /// the color value in the upper 6 bits of each byte.
+    Rgb666,
+    /// RLE compressed Rgb565 pixels.
+    ///
+    /// The data consists of runs, which start with a length byte (1 to 255)
+    /// followed by a single pixel in the [`Rgb565`](Self::Rgb565) format.
+    Rle565,
+    /// RLE compressed Rgb666 pixels.
+    ///
+    /// The data consists of runs, which start with a length byte (1 to 255)
+    /// followed by a single pixel in the [`Rgb666`](Self::Rgb666) format.
+    Rle666,
+}
+
+impl FlashImageFormat {
+    fn is_666(self) -> bool {
+        matches!(self, Self::Rgb666 | Self::Rle666)
+    }
+
+    fn is_compressed(self) -> bool {
+        matches!(self, Self::Rle565 | Self::Rle666)
+    }
+}
+
+/// Byte reader for NOR flash.
+///
+/// The flash is read in aligned chunks of [`CHUNK_SIZE`] bytes.
+struct FlashReader<'f, F> {
+    flash: &'f mut F,
+    offset: u32,
+    buffer: [u8; CHUNK_SIZE],
+    start: usize,
+    end: usize,
+}
+
+impl<'f, F: ReadNorFlash> FlashReader<'f, F> {
+    fn new(flash: &'f mut F, offset: u32) -> Self {
+        assert!(
+            F::READ_SIZE <= CHUNK_SIZE,
+            "flash read size is larger than the chunk size"
+        );
+
+        Self {
+            flash,
+            offset,
+            buffer: [0; CHUNK_SIZE],
+            start: 0,
+            end: 0,
+        }
+    }
+
+    fn fill_buffer(&mut self) -> Result<(), DecodeError<F::Error>> {
+        let read_size = F::READ_SIZE as u32;
+        let base = self.offset - self.offset % read_size;
+
+        let available = (self.flash.capacity() as u32).saturating_sub(base) as usize;
+        let len = available.min(CHUNK_SIZE - CHUNK_SIZE % F::READ_SIZE);
+        let len = len - len % F::READ_SIZE;
+        let start = (self.offset - base) as usize;
+        if start >= len {
+            return Err(DecodeError::UnexpectedEof);
+        }
+
+        self.flash
+            .read(base, &mut self.buffer[..len])
+            .map_err(DecodeError::Read)?;
+        self.start = start;
+        self.end = len;
+
+        Ok(())
+    }
+}
+
+impl<F: ReadNorFlash> ByteReader for FlashReader<'_, F> {
+    type Error = F::Error;
+
+    fn read_exact(&mut self, mut buf: &mut [u8]) -> Result<(), DecodeError<Self::Error>> {
+        while !buf.is_empty() {
+            if self.start == self.end {
+                self.fill_buffer()?;
+            }
+
+            let n = buf.len().min(self.end - self.start);
+            buf[..n].copy_from_slice(&self.buffer[self.start..self.start + n]);
+            self.start += n;
+            self.offset += n as u32;
+            buf = &mut buf[n..];
+        }
+
+        Ok(())
+    }
+}
+
+/// Streaming decoder for raw and RLE compressed flash images.
+///
+/// The pixels are decoded into the color type `C` without an intermediate
+/// 8 bit per channel color, which makes the conversion lossless if `C` is the
+/// color type of the stored pixels.
+struct FlashDecoder<R, C> {
+    reader: R,
+    format: FlashImageFormat,
+    run: u8,
+    pixel: C,
+    remaining: u32,
+}
+
+impl<R, C> FlashDecoder<R, C>
+where
+    R: ByteReader,
+    C: From<Rgb565> + From<Rgb666> + Copy,
+{
+    fn new(reader: R, format: FlashImageFormat, pixel_count: u32) -> Self {
+        Self {
+            reader,
+            format,
+            run: 0,
+            pixel: Rgb565::BLACK.into(),
+            remaining: pixel_count,
+        }
+    }
+
+    /// Reads a single stored pixel.
+    fn read_pixel(&mut self) -> Result<C, DecodeError<R::Error>> {
+        if self.format.is_666() {
+            let mut bytes = [0; 3];
+            self.reader.read_exact(&mut bytes)?;
+            let [r, g, b] = bytes.map(|byte| byte >> 2);
+
+            Ok(Rgb666::new(r, g, b).into())
+        } else {
+            let mut bytes = [0; 2];
+            self.reader.read_exact(&mut bytes)?;
+
+            Ok(Rgb565::from(RawU16::new(u16::from_be_bytes(bytes))).into())
+        }
+    }
+
+    fn decode_pixel(&mut self) -> Result<C, DecodeError<R::Error>> {
+        if !self.format.is_compressed() {
+            return self.read_pixel();
+        }
+
+        if self.run == 0 {
+            self.run = self.reader.read_u8()?;
+            if self.run == 0 {
+                return Err(DecodeError::InvalidFormat);
+            }
+            self.pixel = self.read_pixel()?;
+        }
+        self.run -= 1;
+
+        Ok(self.pixel)
+    }
+}
+
+impl<R, C> Iterator for FlashDecoder<R, C>
+where
+    R: ByteReader,
+    C: From<Rgb565> + From<Rgb666> + Copy,
+{
+    type Item = Result<C, DecodeError<R::Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+
+        Some(self.decode_pixel())
+    }
+}
+
+impl<DI, M, RST, BL, PWR> Display<DI, M, RST, BL, PWR>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word> + From<Rgb565> + From<Rgb666>,
+    RST: OutputPin,
+    BL: Backlight,
+    PWR: OutputPin,
+{
+    /// Draws an image stored in NOR flash.
+    ///
+    /// The image data starts at `offset` in `flash` and fills `area` row by row. The data
+    /// is read in small chunks while it is sent to the display, using a single address
+    /// window. Parts of the image which are outside of the display are clipped.
+    ///
+    /// The stored pixels are converted directly into the color format of the display,
+    /// which means that they are sent unchanged if the formats match.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the read size of the flash is larger than 64 bytes.
+    pub fn draw_flash_image<F: ReadNorFlash>(
+        &mut self,
+        area: &Rectangle,
+        flash: &mut F,
+        offset: u32,
+        format: FlashImageFormat,
+    ) -> Result<(), ImageError<DI::Error, F::Error>> {
+        let reader = FlashReader::new(flash, offset);
+        let decoder = FlashDecoder::<_, M::ColorFormat>::new(
+            reader,
+            format,
+            area.size.width * area.size.height,
+        );
+        self.draw_decoded(area.top_left, area.size, RowOrder::TopDown, decoder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_storage::nor_flash::{ErrorType, NorFlashErrorKind};
+
+    use super::*;
+    use crate::image::SliceReader;
+
+    /// Flash which requires reads to be 4 byte aligned.
+    struct AlignedFlash<'a>(&'a [u8]);
+
+    impl ErrorType for AlignedFlash<'_> {
+        type Error = NorFlashErrorKind;
+    }
+
+    impl ReadNorFlash for AlignedFlash<'_> {
+        const READ_SIZE: usize = 4;
+
+        fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), Self::Error> {
+            let offset = offset as usize;
+            if offset % 4 != 0 || bytes.len() % 4 != 0 {
+                return Err(NorFlashErrorKind::NotAligned);
+            }
+
+            let data = self
+                .0
+                .get(offset..offset + bytes.len())
+                .ok_or(NorFlashErrorKind::OutOfBounds)?;
+            bytes.copy_from_slice(data);
+
+            Ok(())
+        }
+
+        fn capacity(&self) -> usize {
+            self.0.len()
+        }
+    }
+
+    #[test]
+    fn reads_unaligned_offsets() {
+        let mut data = [0; 128];
+        for (i, byte) in data.iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+        let mut flash = AlignedFlash(&data);
+        let mut reader = FlashReader::new(&mut flash, 61);
+
+        let mut buf = [0; 6];
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [61, 62, 63, 64, 65, 66]);
+
+        let mut buf = [0; 62];
+        assert!(matches!(
+            reader.read_exact(&mut buf),
+            Err(DecodeError::UnexpectedEof)
+        ));
+    }
+
+    #[test]
+    fn decodes_raw_pixels() {
+        let data = [0xF8, 0x00, 0x07, 0xE0];
+        let mut decoder =
+            FlashDecoder::<_, Rgb565>::new(SliceReader(&data), FlashImageFormat::Rgb565, 2);
+
+        assert_eq!(decoder.next().unwrap().unwrap(), Rgb565::RED);
+        assert_eq!(decoder.next().unwrap().unwrap(), Rgb565::GREEN);
+        assert!(decoder.next().is_none());
+    }
+
+    #[test]
+    fn decodes_rle_runs() {
+        let data = [2, 0xFC, 0x00, 0x00, 1, 0x00, 0x00, 0xFC, 0, 0, 0, 0];
+        let mut decoder =
+            FlashDecoder::<_, Rgb666>::new(SliceReader(&data), FlashImageFormat::Rle666, 4);
+
+        // 6 bit values are decoded without rounding
+        for expected in [
+            Rgb666::new(63, 0, 0),
+            Rgb666::new(63, 0, 0),
+            Rgb666::new(0, 0, 63),
+        ] {
+            assert_eq!(decoder.next().unwrap().unwrap(), expected);
+        }
+
+        // Runs with a length of 0 are invalid
+        assert!(matches!(
+            decoder.next(),
+            Some(Err(DecodeError::InvalidFormat))
+        ));
+    }
+}
@@ -0,0 +1,308 @@
+use embedded_graphics_core::{
+    geometry::{Point, Size},
+    pixelcolor::Rgb888,
+};
+use embedded_hal::digital::OutputPin;
+
+use super::{ByteReader, DecodeError, ImageError, RowOrder, SliceReader};
+use crate::{
+    backlight::Backlight,
+    interface::{Interface, InterfacePixelFormat},
+    models::Model,
+    Display,
+};
+
+const QOI_MAGIC: [u8; 4] = *b"qoif";
+
+const QOI_OP_INDEX: u8 = 0b0000_0000;
+const QOI_OP_DIFF: u8 = 0b0100_0000;
+const QOI_OP_LUMA: u8 = 0b1000_0000;
+const QOI_OP_RUN: u8 = 0b1100_0000;
+const QOI_OP_RGB: u8 = 0xFE;
+const QOI_OP_RGBA: u8 = 0xFF;
+const QOI_MASK_2: u8 = 0b1100_0000;
+
+/// QOI image header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QoiHeader {
+    /// Image width in pixels.
+    pub width: u32,
+    /// Image height in pixels.
+    pub height: u32,
+    /// Number of color channels (3 = RGB, 4 = RGBA).
+    pub channels: u8,
+    /// Color space (0 = sRGB with linear alpha, 1 = all channels linear).
+    pub colorspace: u8,
+}
+
+impl QoiHeader {
+    /// Size of the header in bytes.
+    pub const SIZE: usize = 14;
+
+    /// Parses a QOI header.
+    ///
+    /// Returns `None` if `bytes` doesn't start with a valid QOI header.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let bytes: &[u8; Self::SIZE] = bytes.get(..Self::SIZE)?.try_into().ok()?;
+
+        if bytes[0..4] != QOI_MAGIC {
+            return None;
+        }
+
+        let header = Self {
+            width: u32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]),
+            height: u32::from_be_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]),
+            channels: bytes[12],
+            colorspace: bytes[13],
+        };
+
+        if !matches!(header.channels, 3 | 4) || header.colorspace > 1 {
+            return None;
+        }
+
+        Some(header)
+    }
+
+    /// Returns the image size.
+    pub fn size(&self) -> Size {
+        Size::new(self.width, self.height)
+    }
+}
+
+/// Streaming QOI decoder.
+///
+/// Uses a fixed 256 byte color index and never buffers decoded pixels.
+pub(crate) struct QoiDecoder<R> {
+    reader: R,
+    index: [[u8; 4]; 64],
+    pixel: [u8; 4],
+    run: u8,
+    remaining: u32,
+}
+
+impl<R: ByteReader> QoiDecoder<R> {
+    /// Reads the header and creates a decoder for the pixel data.
+    pub fn new(mut reader: R) -> Result<(Self, QoiHeader), DecodeError<R::Error>> {
+        let mut bytes = [0; QoiHeader::SIZE];
+        reader.read_exact(&mut bytes)?;
+        let header = QoiHeader::from_bytes(&bytes).ok_or(DecodeError::InvalidFormat)?;
+
+        let decoder = Self {
+            reader,
+            index: [[0; 4]; 64],
+            pixel: [0, 0, 0, 255],
+            run: 0,
+            remaining: header.width.saturating_mul(header.height),
+        };
+
+        Ok((decoder, header))
+    }
+
+    fn decode_pixel(&mut self) -> Result<(), DecodeError<R::Error>> {
+        if self.run > 0 {
+            self.run -= 1;
+            return Ok(());
+        }
+
+        let op = self.reader.read_u8()?;
+        let [r, g, b, a] = &mut self.pixel;
+
+        match op {
+            QOI_OP_RGB => {
+                let mut rgb = [0; 3];
+                self.reader.read_exact(&mut rgb)?;
+                [*r, *g, *b] = rgb;
+            }
+            QOI_OP_RGBA => {
+                let mut rgba = [0; 4];
+                self.reader.read_exact(&mut rgba)?;
+                [*r, *g, *b, *a] = rgba;
+            }
+            _ => match op & QOI_MASK_2 {
+                QOI_OP_INDEX => {
+                    self.pixel = self.index[usize::from(op)];
+                }
+                QOI_OP_DIFF => {
+                    *r = r.wrapping_add((op >> 4) & 0x03).wrapping_sub(2);
+                    *g = g.wrapping_add((op >> 2) & 0x03).wrapping_sub(2);
+                    *b = b.wrapping_add(op & 0x03).wrapping_sub(2);
+                }
+                QOI_OP_LUMA => {
+                    let next = self.reader.read_u8()?;
+                    let dg = (op & 0x3F).wrapping_sub(32);
+                    *r = r.wrapping_add(dg.wrapping_add(next >> 4).wrapping_sub(8));
+                    *g = g.wrapping_add(dg);
+                    *b = b.wrapping_add(dg.wrapping_add(next & 0x0F).wrapping_sub(8));
+                }
+                QOI_OP_RUN => {
+                    // The current pixel is repeated
+                    self.run = op & 0x3F;
+                }
+                _ => unreachable!(),
+            },
+        }
+
+        let [r, g, b, a] = self.pixel;
+        let hash = (r.wrapping_mul(3))
+            .wrapping_add(g.wrapping_mul(5))
+            .wrapping_add(b.wrapping_mul(7))
+            .wrapping_add(a.wrapping_mul(11));
+        self.index[usize::from(hash % 64)] = self.pixel;
+
+        Ok(())
+    }
+}
+
+impl<R: ByteReader> Iterator for QoiDecoder<R> {
+    type Item = Result<Rgb888, DecodeError<R::Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+
+        Some(self.decode_pixel().map(|()| {
+            let [r, g, b, _] = self.pixel;
+            Rgb888::new(r, g, b)
+        }))
+    }
+}
+
+impl<DI, M, RST, BL, PWR> Display<DI, M, RST, BL, PWR>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word> + From<Rgb888>,
+    RST: OutputPin,
+    BL: Backlight,
+    PWR: OutputPin,
+{
+    /// Draws a QOI image.
+    ///
+    /// The image is decoded while it is sent to the display, using a single address window.
+    /// The top left corner of the image is placed at `position` and parts of the image which
+    /// are outside of the display are clipped. The alpha channel is ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use embedded_graphics::prelude::*;
+    ///
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// // 1x1 pixel red image
+    /// let image = [
+    ///     b'q', b'o', b'i', b'f', 0, 0, 0, 1, 0, 0, 0, 1, 3, 0, //
+    ///     0xFE, 0xFF, 0x00, 0x00, //
+    ///     0, 0, 0, 0, 0, 0, 0, 1,
+    /// ];
+    ///
+    /// display.draw_qoi(Point::new(10, 20), &image).unwrap();
+    /// ```
+    pub fn draw_qoi(
+        &mut self,
+        position: Point,
+        data: &[u8],
+    ) -> Result<(), ImageError<DI::Error, core::convert::Infallible>> {
+        self.draw_qoi_from(position, SliceReader(data))
+    }
+
+    /// Draws a QOI image read from an [`embedded_io::Read`] source.
+    ///
+    /// See [`draw_qoi`](Self::draw_qoi) for more information.
+    #[cfg(feature = "embedded-io")]
+    pub fn draw_qoi_stream<R: embedded_io::Read>(
+        &mut self,
+        position: Point,
+        reader: &mut R,
+    ) -> Result<(), ImageError<DI::Error, R::Error>> {
+        self.draw_qoi_from(position, super::IoReader::new(reader))
+    }
+
+    fn draw_qoi_from<B: ByteReader>(
+        &mut self,
+        position: Point,
+        reader: B,
+    ) -> Result<(), ImageError<DI::Error, B::Error>> {
+        let (decoder, header) = QoiDecoder::new(reader)?;
+        self.draw_decoded(position, header.size(), RowOrder::TopDown, decoder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEADER_2X2: [u8; 14] = [b'q', b'o', b'i', b'f', 0, 0, 0, 2, 0, 0, 0, 2, 3, 0];
+
+    fn decode(ops: &[u8]) -> ([Rgb888; 4], usize) {
+        let mut data = [0; 32];
+        data[..14].copy_from_slice(&HEADER_2X2);
+        data[14..14 + ops.len()].copy_from_slice(ops);
+
+        let (decoder, _) = QoiDecoder::new(SliceReader(&data[..14 + ops.len()])).unwrap();
+        let mut pixels = [Rgb888::new(0, 0, 0); 4];
+        let mut count = 0;
+        for (pixel, decoded) in pixels.iter_mut().zip(decoder) {
+            *pixel = decoded.unwrap();
+            count += 1;
+        }
+        (pixels, count)
+    }
+
+    #[test]
+    fn parses_header() {
+        let header = QoiHeader::from_bytes(&HEADER_2X2).unwrap();
+        assert_eq!(header.size(), Size::new(2, 2));
+        assert_eq!(header.channels, 3);
+
+        assert_eq!(QoiHeader::from_bytes(&HEADER_2X2[..13]), None);
+        assert_eq!(QoiHeader::from_bytes(b"qoix0000000030"), None);
+    }
+
+    #[test]
+    fn decodes_ops() {
+        let (pixels, count) = decode(&[
+            QOI_OP_RGB,
+            100,
+            150,
+            200,                      // rgb
+            QOI_OP_DIFF | 0b11_01_10, // diff: r + 1, g - 1, b + 0
+            QOI_OP_LUMA | 40,
+            0x9A,             // luma: dg = 8, dr = 8 + 1, db = 8 + 2
+            QOI_OP_INDEX | 7, // index of the first pixel
+        ]);
+
+        assert_eq!(count, 4);
+        assert_eq!(
+            pixels,
+            [
+                Rgb888::new(100, 150, 200),
+                Rgb888::new(101, 149, 200),
+                Rgb888::new(110, 157, 210),
+                Rgb888::new(100, 150, 200),
+            ]
+        );
+    }
+
+    #[test]
+    fn decodes_runs() {
+        let (pixels, count) = decode(&[QOI_OP_RGB, 1, 2, 3, QOI_OP_RUN | 2]);
+
+        assert_eq!(count, 4);
+        assert_eq!(pixels, [Rgb888::new(1, 2, 3); 4]);
+    }
+
+    #[test]
+    fn reports_truncated_data() {
+        let mut data = [0; 17];
+        data[..14].copy_from_slice(&HEADER_2X2);
+        data[14..].copy_from_slice(&[QOI_OP_RGB, 1, 2]);
+
+        let (mut decoder, _) = QoiDecoder::new(SliceReader(&data)).unwrap();
+        assert!(matches!(
+            decoder.next(),
+            Some(Err(DecodeError::UnexpectedEof))
+        ));
+    }
+}
@@ -0,0 +1,248 @@
+use embedded_graphics_core::{
+    geometry::{Point, Size},
+    pixelcolor::Rgb888,
+};
+use embedded_hal::digital::OutputPin;
+
+use super::{ByteReader, DecodeError, ImageError, PixelLayout, RowOrder, SliceReader};
+use crate::{
+    backlight::Backlight,
+    interface::{Interface, InterfacePixelFormat},
+    models::Model,
+    Display,
+};
+
+const HEADER_SIZE: usize = 18;
+
+/// Parsed TGA header.
+struct TgaHeader {
+    size: Size,
+    row_order: RowOrder,
+    layout: PixelLayout,
+    compressed: bool,
+}
+
+impl TgaHeader {
+    /// Reads the header and skips to the start of the pixel data.
+    fn read<B: ByteReader>(reader: &mut B) -> Result<Self, DecodeError<B::Error>> {
+        let mut bytes = [0; HEADER_SIZE];
+        reader.read_exact(&mut bytes)?;
+
+        let u16_at = |i: usize| u16::from_le_bytes([bytes[i], bytes[i + 1]]);
+
+        let id_length = usize::from(bytes[0]);
+        let color_map_type = bytes[1];
+        let image_type = bytes[2];
+        let color_map_length = usize::from(u16_at(5));
+        let color_map_entry_size = usize::from(bytes[7]);
+        let width = u16_at(12);
+        let height = u16_at(14);
+        let bpp = bytes[16];
+        let descriptor = bytes[17];
+
+        // Only true color and grayscale images are supported, color map
+        // data is allowed to be present but isn't used by these image types
+        let (layout, compressed) = match (image_type, bpp) {
+            (2 | 10, 15 | 16) => (PixelLayout::Rgb555, image_type == 10),
+            (2 | 10, 24) => (PixelLayout::Bgr888, image_type == 10),
+            (2 | 10, 32) => (PixelLayout::Bgra8888, image_type == 10),
+            (3 | 11, 8) => (PixelLayout::Gray8, image_type == 11),
+            _ => return Err(DecodeError::InvalidFormat),
+        };
+
+        // Right to left images aren't supported
+        if color_map_type > 1 || descriptor & 0x10 != 0 || width == 0 || height == 0 {
+            return Err(DecodeError::InvalidFormat);
+        }
+
+        let color_map_size = if color_map_type == 1 {
+            color_map_length * color_map_entry_size.div_ceil(8)
+        } else {
+            0
+        };
+        reader.skip(id_length + color_map_size)?;
+
+        let row_order = if descriptor & 0x20 != 0 {
+            RowOrder::TopDown
+        } else {
+            RowOrder::BottomUp
+        };
+
+        Ok(Self {
+            size: Size::new(u32::from(width), u32::from(height)),
+            row_order,
+            layout,
+            compressed,
+        })
+    }
+}
+
+/// Streaming decoder for uncompressed and RLE compressed TGA images.
+struct TgaDecoder<R> {
+    reader: R,
+    layout: PixelLayout,
+    compressed: bool,
+    // Remaining pixels in the current RLE packet
+    packet_remaining: u8,
+    // The current RLE packet is a run of `pixel`
+    repeat: bool,
+    pixel: Rgb888,
+    remaining: u32,
+}
+
+impl<R: ByteReader> TgaDecoder<R> {
+    fn new(reader: R, header: &TgaHeader) -> Self {
+        Self {
+            reader,
+            layout: header.layout,
+            compressed: header.compressed,
+            packet_remaining: 0,
+            repeat: false,
+            pixel: Rgb888::new(0, 0, 0),
+            remaining: header.size.width * header.size.height,
+        }
+    }
+
+    fn decode_pixel(&mut self) -> Result<Rgb888, DecodeError<R::Error>> {
+        if !self.compressed {
+            return self.layout.read(&mut self.reader);
+        }
+
+        if self.packet_remaining == 0 {
+            let packet = self.reader.read_u8()?;
+            self.packet_remaining = (packet & 0x7F) + 1;
+            self.repeat = packet & 0x80 != 0;
+
+            if self.repeat {
+                self.pixel = self.layout.read(&mut self.reader)?;
+            }
+        }
+
+        if !self.repeat {
+            self.pixel = self.layout.read(&mut self.reader)?;
+        }
+        self.packet_remaining -= 1;
+
+        Ok(self.pixel)
+    }
+}
+
+impl<R: ByteReader> Iterator for TgaDecoder<R> {
+    type Item = Result<Rgb888, DecodeError<R::Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+
+        Some(self.decode_pixel())
+    }
+}
+
+impl<DI, M, RST, BL, PWR> Display<DI, M, RST, BL, PWR>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word> + From<Rgb888>,
+    RST: OutputPin,
+    BL: Backlight,
+    PWR: OutputPin,
+{
+    /// Draws a TGA image.
+    ///
+    /// Uncompressed and RLE compressed true color (16, 24 and 32 bit per pixel) and grayscale
+    /// (8 bit per pixel) images are supported. The top left corner of the image is placed at
+    /// `position` and parts of the image which are outside of the display are clipped.
+    ///
+    /// Top down images are sent using a single address window. Bottom up images are sent
+    /// using one address window per row to draw them without buffering any rows.
+    pub fn draw_tga(
+        &mut self,
+        position: Point,
+        data: &[u8],
+    ) -> Result<(), ImageError<DI::Error, core::convert::Infallible>> {
+        self.draw_tga_from(position, SliceReader(data))
+    }
+
+    /// Draws a TGA image read from an [`embedded_io::Read`] source, like a file on an SD card.
+    ///
+    /// See [`draw_tga`](Self::draw_tga) for more information.
+    #[cfg(feature = "embedded-io")]
+    pub fn draw_tga_stream<R: embedded_io::Read>(
+        &mut self,
+        position: Point,
+        reader: &mut R,
+    ) -> Result<(), ImageError<DI::Error, R::Error>> {
+        self.draw_tga_from(position, super::IoReader::new(reader))
+    }
+
+    fn draw_tga_from<B: ByteReader>(
+        &mut self,
+        position: Point,
+        mut reader: B,
+    ) -> Result<(), ImageError<DI::Error, B::Error>> {
+        let header = TgaHeader::read(&mut reader)?;
+        let decoder = TgaDecoder::new(reader, &header);
+        self.draw_decoded(position, header.size, header.row_order, decoder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(image_type: u8, bpp: u8, descriptor: u8) -> [u8; HEADER_SIZE] {
+        let mut header = [0; HEADER_SIZE];
+        header[2] = image_type;
+        header[12..14].copy_from_slice(&2u16.to_le_bytes());
+        header[14..16].copy_from_slice(&2u16.to_le_bytes());
+        header[16] = bpp;
+        header[17] = descriptor;
+        header
+    }
+
+    #[test]
+    fn parses_row_order() {
+        let parsed = TgaHeader::read(&mut SliceReader(&header(2, 24, 0))).unwrap();
+        assert_eq!(parsed.size, Size::new(2, 2));
+        assert_eq!(parsed.row_order, RowOrder::BottomUp);
+        assert!(!parsed.compressed);
+
+        let parsed = TgaHeader::read(&mut SliceReader(&header(10, 24, 0x20))).unwrap();
+        assert_eq!(parsed.row_order, RowOrder::TopDown);
+        assert!(parsed.compressed);
+    }
+
+    #[test]
+    fn decodes_rle_packets() {
+        let mut data = [0; HEADER_SIZE + 5];
+        data[..HEADER_SIZE].copy_from_slice(&header(11, 8, 0x20));
+        data[HEADER_SIZE..].copy_from_slice(&[
+            // run of 3 pixels
+            0x82, 10, //
+            // 1 raw pixel
+            0x00, 20, //
+            // truncated packet
+            0xFF,
+        ]);
+
+        let mut reader = SliceReader(&data);
+        let header = TgaHeader::read(&mut reader).unwrap();
+        let mut decoder = TgaDecoder::new(reader, &header);
+
+        for expected in [10, 10, 10, 20] {
+            let gray = Rgb888::new(expected, expected, expected);
+            assert_eq!(decoder.next().unwrap().unwrap(), gray);
+        }
+        assert!(decoder.next().is_none());
+    }
+
+    #[test]
+    fn rejects_color_mapped_images() {
+        assert!(matches!(
+            TgaHeader::read(&mut SliceReader(&header(1, 8, 0))),
+            Err(DecodeError::InvalidFormat)
+        ));
+    }
+}
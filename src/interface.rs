@@ -40,6 +40,21 @@ pub trait Interface {
     ) -> Result<(), Self::Error>;
 }
 
+/// Interface which can read data from the display controller.
+///
+/// The interfaces in this crate are write only and don't implement this trait,
+/// because reading requires additional hardware support, like a connected MISO
+/// or RDX line, and controller specific timing, like dummy clock cycles. It can
+/// be implemented for custom interfaces to use read based features like
+/// [`Display::check_health`](crate::Display::check_health).
+pub trait ReadInterface: Interface {
+    /// Sends a read command and reads the response into `data`.
+    ///
+    /// Dummy cycles which are required by the display controller must be
+    /// handled by the implementation and are not included in `data`.
+    fn read_command(&mut self, command: u8, data: &mut [u8]) -> Result<(), Self::Error>;
+}
+
 impl<T: Interface> Interface for &mut T {
     type Word = T::Word;
     type Error = T::Error;
@@ -64,6 +79,12 @@ impl<T: Interface> Interface for &mut T {
     }
 }
 
+impl<T: ReadInterface> ReadInterface for &mut T {
+    fn read_command(&mut self, command: u8, data: &mut [u8]) -> Result<(), Self::Error> {
+        T::read_command(self, command, data)
+    }
+}
+
 fn rgb565_to_bytes(pixel: Rgb565) -> [u8; 2] {
     embedded_graphics_core::pixelcolor::raw::ToBytes::to_be_bytes(pixel)
 }
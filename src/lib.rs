@@ -14,6 +14,20 @@
 //!
 //! An optional batching of draws is supported via the `batch` feature (default on)
 //!
+//! Draw target adapters, e.g. for dithering higher color depth images, are
+//! available in the [adapters] module.
+//!
+//! Images can be decoded and drawn without buffering them in RAM by using the
+//! methods in the [image] module. Reading images from `embedded_io::Read`
+//! sources requires the `embedded-io` feature and reading raw or RLE compressed
+//! images from external NOR flash requires the `embedded-storage` feature.
+//!
+//! Compact delta RLE animations, e.g. for boot animations, can be created and
+//! played using the [animation] module.
+//!
+//! A text console which uses hardware scrolling is available in the `console`
+//! module if the `embedded-graphics` feature is enabled.
+//!
 //! ### List of supported models
 //!
 //! * GC9107
@@ -105,6 +119,12 @@ use dcs::InterfaceExt;
 
 pub mod interface;
 
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{OriginDimensions, Point},
+    pixelcolor::RgbColor,
+    primitives::Rectangle,
+};
 use embedded_hal::delay::DelayNs;
 use embedded_hal::digital::OutputPin;
 
@@ -113,7 +133,37 @@ use interface::InterfacePixelFormat;
 use options::MemoryMapping;
 
 mod builder;
-pub use builder::{Builder, NoResetPin};
+pub use builder::{Builder, InitError, NoPowerPin, NoResetPin};
+
+pub mod backlight;
+use backlight::{Backlight, NoBacklight};
+
+mod power;
+pub use power::{PowerError, PowerState, StateError};
+
+mod nonblocking;
+pub use nonblocking::InitSequence;
+
+mod scroll;
+pub use scroll::ScrollError;
+
+mod page_flip;
+pub use page_flip::PageFlipError;
+
+mod tearing;
+pub use tearing::TearingError;
+
+mod partial;
+pub use partial::PartialAreaError;
+
+mod idle;
+pub use idle::Pixels3bppError;
+
+mod health;
+pub use health::Diagnosis;
+
+mod brightness;
+pub use brightness::BrightnessError;
 
 pub mod dcs;
 
@@ -122,6 +172,15 @@ use models::Model;
 
 mod graphics;
 
+pub mod adapters;
+
+pub mod image;
+
+pub mod animation;
+
+#[cfg(feature = "embedded-graphics")]
+pub mod console;
+
 mod test_image;
 pub use test_image::TestImage;
 
@@ -131,12 +190,14 @@ mod batch;
 ///
 /// Display driver to connect to TFT displays.
 ///
-pub struct Display<DI, MODEL, RST>
+pub struct Display<DI, MODEL, RST, BL = NoBacklight, PWR = NoPowerPin>
 where
     DI: interface::Interface,
     MODEL: Model,
     MODEL::ColorFormat: InterfacePixelFormat<DI::Word>,
     RST: OutputPin,
+    BL: Backlight,
+    PWR: OutputPin,
 {
     // DCS provider
     di: DI,
@@ -144,20 +205,43 @@ where
     model: MODEL,
     // Reset pin
     rst: Option<RST>,
+    // Backlight
+    backlight: Option<BL>,
+    // Backlight level, restored after the display was woken up
+    backlight_level: u8,
+    // Power enable pin
+    power: Option<PWR>,
+    // Time the supply needs to settle after power on, in microseconds
+    power_settle_us: u32,
     // Model Options, includes current orientation
     options: options::ModelOptions,
     // Current MADCTL value copy for runtime updates
     madctl: dcs::SetAddressMode,
-    // State monitor for sleeping TODO: refactor to a Model-connected state machine
-    sleeping: bool,
+    // Power state of the controller
+    state: PowerState,
+    // Current VSCRDEF value, used to translate scroll offsets
+    scroll_area: dcs::SetScrollArea,
+    // Current VSCRSAD value, restored by reinit
+    scroll_start: u16,
+    // Tearing effect settings, restored by reinit if they were changed after init
+    tearing_effect: Option<options::TearingEffect>,
+    tear_scanline: Option<u16>,
+    // First framebuffer row of the off-screen page, if page flipping is enabled
+    draw_page: Option<u16>,
+    // Last partial area, restored by reinit if partial mode is enabled
+    partial_area: Option<partial::PartialArea>,
+    // Sleep or wake transition started by a non-blocking method
+    pending: Option<nonblocking::PendingTransition>,
 }
 
-impl<DI, M, RST> Display<DI, M, RST>
+impl<DI, M, RST, BL, PWR> Display<DI, M, RST, BL, PWR>
 where
     DI: interface::Interface,
     M: Model,
     M::ColorFormat: InterfacePixelFormat<DI::Word>,
     RST: OutputPin,
+    BL: Backlight,
+    PWR: OutputPin,
 {
     ///
     /// Returns currently set [options::Orientation]
@@ -166,6 +250,37 @@ where
         self.options.orientation
     }
 
+    /// Returns the [visible area](options::VisibleArea) of the display.
+    pub fn visible_area(&self) -> options::VisibleArea {
+        self.options.visible_area
+    }
+
+    /// Returns the visible columns in row `y`.
+    ///
+    /// Returns `None` if no pixel in the row is visible. This can be used by
+    /// layout code to place content inside the visible area of round displays.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// // The whole 240x320 display is visible.
+    /// assert_eq!(display.visible_columns(0), Some(0..=239));
+    /// assert_eq!(display.visible_columns(320), None);
+    /// ```
+    pub fn visible_columns(&self, y: i32) -> Option<core::ops::RangeInclusive<i32>> {
+        let y = u32::try_from(y).ok()?;
+        let (start, end) = self.options.visible_area.columns(self.size(), y)?;
+
+        Some(start as i32..=end as i32)
+    }
+
+    /// Returns `true` if the given point is visible.
+    pub fn is_visible(&self, point: Point) -> bool {
+        self.visible_columns(point.y)
+            .is_some_and(|columns| columns.contains(&point.x))
+    }
+
     ///
     /// Sets display [options::Orientation] with mirror image parameter
     ///
@@ -179,7 +294,82 @@ where
     /// ```
     pub fn set_orientation(&mut self, orientation: options::Orientation) -> Result<(), DI::Error> {
         self.madctl = self.madctl.with_orientation(orientation); // set orientation
-        self.di.write_command(self.madctl)?;
+        if self.state.powered {
+            self.di.write_command(self.madctl)?;
+        }
+        self.options.orientation = orientation;
+
+        Ok(())
+    }
+
+    /// Returns the current [options::ModelOptions].
+    ///
+    /// The returned options reflect all changes made at runtime, e.g. by using
+    /// [`set_orientation`](Self::set_orientation) or
+    /// [`set_color_order`](Self::set_color_order).
+    pub fn options(&self) -> &options::ModelOptions {
+        &self.options
+    }
+
+    ///
+    /// Sets the color inversion.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mipidsi::options::ColorInversion;
+    ///
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// display.set_invert_colors(ColorInversion::Inverted).unwrap();
+    /// assert_eq!(display.options().invert_colors, ColorInversion::Inverted);
+    /// ```
+    pub fn set_invert_colors(
+        &mut self,
+        invert_colors: options::ColorInversion,
+    ) -> Result<(), DI::Error> {
+        if self.state.powered {
+            self.di
+                .write_command(dcs::SetInvertMode::new(invert_colors))?;
+        }
+        self.options.invert_colors = invert_colors;
+
+        Ok(())
+    }
+
+    ///
+    /// Sets the [options::ColorOrder].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mipidsi::options::ColorOrder;
+    ///
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// display.set_color_order(ColorOrder::Bgr).unwrap();
+    /// assert_eq!(display.options().color_order, ColorOrder::Bgr);
+    /// ```
+    pub fn set_color_order(&mut self, color_order: options::ColorOrder) -> Result<(), DI::Error> {
+        self.madctl = self.madctl.with_color_order(color_order);
+        if self.state.powered {
+            self.di.write_command(self.madctl)?;
+        }
+        self.options.color_order = color_order;
+
+        Ok(())
+    }
+
+    ///
+    /// Sets the [options::RefreshOrder].
+    ///
+    pub fn set_refresh_order(
+        &mut self,
+        refresh_order: options::RefreshOrder,
+    ) -> Result<(), DI::Error> {
+        self.madctl = self.madctl.with_refresh_order(refresh_order);
+        if self.state.powered {
+            self.di.write_command(self.madctl)?;
+        }
+        self.options.refresh_order = refresh_order;
 
         Ok(())
     }
@@ -213,6 +403,8 @@ where
     /// checking is performed on the `colors` iterator and drawing will wrap around if the
     /// iterator returns more color values than the number of pixels in the given region.
     ///
+    /// Does nothing while the display is powered off, see [`power_off`](Self::power_off).
+    ///
     /// This is a low level function, which isn't intended to be used in regular user code.
     /// Consider using the [`fill_contiguous`](https://docs.rs/embedded-graphics/latest/embedded_graphics/draw_target/trait.DrawTarget.html#method.fill_contiguous)
     /// function from the `embedded-graphics` crate as an alternative instead.
@@ -243,6 +435,10 @@ where
     where
         T: IntoIterator<Item = M::ColorFormat>,
     {
+        if !self.state.powered {
+            return Ok(());
+        }
+
         self.set_address_window(sx, sy, ex, ey)?;
 
         self.di.write_command(dcs::WriteMemoryStart)?;
@@ -250,6 +446,46 @@ where
         M::ColorFormat::send_pixels(&mut self.di, colors)
     }
 
+    /// Draws an indexed color image.
+    ///
+    /// The palette indices in `data` are expanded to the display color format while the
+    /// image is sent to the display, which allows images to be stored in a compact format
+    /// without requiring a framebuffer in RAM. All pixels are sent using a single address window.
+    ///
+    /// `data` contains the indices for each row of `area`, packed using the given
+    /// [`IndexBits`](options::IndexBits) with the most significant bits first. Each row starts at
+    /// a byte boundary. Indices which are outside of `palette` are drawn as black and rows which
+    /// aren't covered by `data` aren't drawn. Parts of `area` which are outside of the display
+    /// are clipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use embedded_graphics::{pixelcolor::Rgb565, prelude::*, primitives::Rectangle};
+    /// use mipidsi::options::IndexBits;
+    ///
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// let palette = [Rgb565::BLACK, Rgb565::RED, Rgb565::GREEN, Rgb565::BLUE];
+    ///
+    /// // 4x2 pixel icon using 2 bits per pixel
+    /// let icon = [0b00_01_10_11, 0b11_10_01_00];
+    ///
+    /// let area = Rectangle::new(Point::new(10, 10), Size::new(4, 2));
+    /// display.draw_indexed(&area, &icon, IndexBits::Two, &palette).unwrap();
+    /// ```
+    pub fn draw_indexed(
+        &mut self,
+        area: &Rectangle,
+        data: &[u8],
+        bits: options::IndexBits,
+        palette: &[M::ColorFormat],
+    ) -> Result<(), DI::Error> {
+        let colors =
+            graphics::indexed_colors(data, area.size.width, bits, palette, M::ColorFormat::BLACK);
+
+        self.fill_contiguous(area, colors)
+    }
+
     /// Sets the vertical scroll region.
     ///
     /// The `top_fixed_area` and `bottom_fixed_area` arguments can be used to
@@ -258,7 +494,8 @@ where
     ///
     /// Note that this method is not affected by the current display orientation
     /// and will always scroll vertically relative to the default display
-    /// orientation.
+    /// orientation. Use [`set_scroll_region`](Self::set_scroll_region) to define
+    /// the scroll region relative to the current orientation.
     ///
     /// The combined height of the fixed area must not larger than the
     /// height of the framebuffer height in the default orientation.
@@ -282,7 +519,12 @@ where
             )
         };
 
-        self.di.write_command(vscrdef)
+        if self.state.powered {
+            self.di.write_command(vscrdef.clone())?;
+        }
+        self.scroll_area = vscrdef;
+
+        Ok(())
     }
 
     /// Sets the vertical scroll offset.
@@ -293,8 +535,12 @@ where
     /// Use [`set_vertical_scroll_region`](Self::set_vertical_scroll_region) to setup the scroll region, before
     /// using this method.
     pub fn set_vertical_scroll_offset(&mut self, offset: u16) -> Result<(), DI::Error> {
-        let vscad = dcs::SetScrollStart::new(offset);
-        self.di.write_command(vscad)
+        if self.state.powered {
+            self.di.write_command(dcs::SetScrollStart::new(offset))?;
+        }
+        self.scroll_start = offset;
+
+        Ok(())
     }
 
     ///
@@ -307,8 +553,20 @@ where
 
     // Sets the address window for the display.
     fn set_address_window(&mut self, sx: u16, sy: u16, ex: u16, ey: u16) -> Result<(), DI::Error> {
+        let offset = self.address_offset();
+        let (sx, sy, ex, ey) = (sx + offset.0, sy + offset.1, ex + offset.0, ey + offset.1);
+
+        self.di.write_command(dcs::SetColumnAddress::new(sx, ex))?;
+        self.di.write_command(dcs::SetPageAddress::new(sy, ey))
+    }
+
+    /// Returns the offset between display coordinates and framebuffer addresses.
+    fn address_offset(&self) -> (u16, u16) {
         // add clipping offsets if present
         let mut offset = self.options.display_offset;
+        if let Some(row) = self.draw_page {
+            offset.1 = row;
+        }
         let mapping = MemoryMapping::from(self.options.orientation);
         if mapping.reverse_columns {
             offset.0 = M::FRAMEBUFFER_SIZE.0 - (self.options.display_size.0 + offset.0);
@@ -320,10 +578,7 @@ where
             offset = (offset.1, offset.0);
         }
 
-        let (sx, sy, ex, ey) = (sx + offset.0, sy + offset.1, ex + offset.0, ey + offset.1);
-
-        self.di.write_command(dcs::SetColumnAddress::new(sx, ex))?;
-        self.di.write_command(dcs::SetPageAddress::new(sy, ey))
+        offset
     }
 
     ///
@@ -333,37 +588,164 @@ where
         &mut self,
         tearing_effect: options::TearingEffect,
     ) -> Result<(), DI::Error> {
-        self.di
-            .write_command(dcs::SetTearingEffect::new(tearing_effect))
+        if self.state.powered {
+            self.di
+                .write_command(dcs::SetTearingEffect::new(tearing_effect))?;
+        }
+        self.tearing_effect = Some(tearing_effect);
+
+        Ok(())
+    }
+
+    ///
+    /// Sets the scanline at which the tearing effect output is triggered.
+    ///
+    /// By default the TE signal is triggered at the start of the vertical
+    /// blanking period. Setting a later scanline can be used to start a flush
+    /// while the display is still scanning out the previous frame, if the
+    /// flush is guaranteed to stay behind the scan position.
+    ///
+    pub fn set_tear_scanline(&mut self, scanline: u16) -> Result<(), DI::Error> {
+        if self.state.powered {
+            self.di.write_command(dcs::SetTearScanline::new(scanline))?;
+        }
+        self.tear_scanline = Some(scanline);
+
+        Ok(())
     }
 
     ///
     /// Returns `true` if display is currently set to sleep.
     ///
     pub fn is_sleeping(&self) -> bool {
-        self.sleeping
+        self.state.sleeping
+    }
+
+    ///
+    /// Returns the current [PowerState] of the display controller.
+    ///
+    pub fn power_state(&self) -> PowerState {
+        self.state
+    }
+
+    ///
+    /// Returns `true` if the display output is turned on.
+    ///
+    pub fn is_display_on(&self) -> bool {
+        self.state.display_on
+    }
+
+    ///
+    /// Turns the display output on or off.
+    ///
+    /// While the display output is turned off the display shows a blank screen,
+    /// but the framebuffer can still be updated. This can be used to hide the
+    /// content while the display is redrawn, e.g. after the orientation was
+    /// changed. Unlike [Self::sleep] this doesn't require any delays.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use embedded_graphics::{pixelcolor::Rgb565, prelude::*};
+    /// use mipidsi::options::{Orientation, Rotation};
+    ///
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// display.set_display_on(false).unwrap();
+    /// display.set_orientation(Orientation::new().rotate(Rotation::Deg90)).unwrap();
+    /// display.clear(Rgb565::BLACK).unwrap();
+    /// display.set_display_on(true).unwrap();
+    /// ```
+    ///
+    /// Returns [StateError::PoweredOff] if the display is powered off and
+    /// [StateError::Sleeping] if the display is sleeping.
+    pub fn set_display_on(&mut self, on: bool) -> Result<(), StateError<DI::Error>> {
+        self.check_awake()?;
+        if on {
+            self.di
+                .write_command(dcs::SetDisplayOn)
+                .map_err(StateError::Interface)?;
+        } else {
+            self.di
+                .write_command(dcs::SetDisplayOff)
+                .map_err(StateError::Interface)?;
+        }
+        self.state.display_on = on;
+
+        Ok(())
     }
 
     ///
     /// Puts the display to sleep, reducing power consumption.
-    /// Need to call [Self::wake] before issuing other commands
     ///
-    pub fn sleep<D: DelayNs>(&mut self, delay: &mut D) -> Result<(), DI::Error> {
-        self.di.write_command(dcs::EnterSleepMode)?;
-        // All supported models requires a 120ms delay before issuing other commands
-        delay.delay_us(120_000);
-        self.sleeping = true;
+    /// The backlight is turned off after the display has entered sleep mode.
+    /// This method blocks for [Model::SLEEP_DELAY_US] after the command was
+    /// sent, to make sure that the display isn't woken up too early.
+    ///
+    /// Changing the display output, idle or partial mode is rejected while the
+    /// display is sleeping. Drawing is still allowed, because the controller
+    /// keeps accepting writes to its frame memory in sleep mode, and the
+    /// updated contents are shown after [Self::wake].
+    ///
+    /// Returns [SleepError::AlreadySleeping] if the display is already sleeping
+    /// and [SleepError::PoweredOff] if the display is powered off.
+    ///
+    pub fn sleep<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<(), SleepError<DI::Error, BL::Error>> {
+        if !self.state.powered {
+            return Err(SleepError::PoweredOff);
+        }
+        if self.pending.is_some() {
+            return Err(SleepError::Busy);
+        }
+        if self.state.sleeping {
+            return Err(SleepError::AlreadySleeping);
+        }
+        self.di
+            .write_command(dcs::EnterSleepMode)
+            .map_err(SleepError::Interface)?;
+        self.state.sleeping = true;
+        if let Some(backlight) = &mut self.backlight {
+            backlight.set_level(0).map_err(SleepError::Backlight)?;
+        }
+        delay.delay_us(M::SLEEP_DELAY_US);
         Ok(())
     }
 
     ///
     /// Wakes the display after it's been set to sleep via [Self::sleep]
     ///
-    pub fn wake<D: DelayNs>(&mut self, delay: &mut D) -> Result<(), DI::Error> {
-        self.di.write_command(dcs::ExitSleepMode)?;
-        // ST7789 and st7735s have the highest minimal delay of 120ms
-        delay.delay_us(120_000);
-        self.sleeping = false;
+    /// The backlight level which was set before the display was put to sleep is
+    /// restored after the display has woken up. This method blocks for
+    /// [Model::SLEEP_DELAY_US] after the command was sent.
+    ///
+    /// Returns [SleepError::NotSleeping] if the display isn't sleeping and
+    /// [SleepError::PoweredOff] if the display is powered off.
+    ///
+    pub fn wake<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<(), SleepError<DI::Error, BL::Error>> {
+        if !self.state.powered {
+            return Err(SleepError::PoweredOff);
+        }
+        if self.pending.is_some() {
+            return Err(SleepError::Busy);
+        }
+        if !self.state.sleeping {
+            return Err(SleepError::NotSleeping);
+        }
+        self.di
+            .write_command(dcs::ExitSleepMode)
+            .map_err(SleepError::Interface)?;
+        delay.delay_us(M::SLEEP_DELAY_US);
+        self.state.sleeping = false;
+        if let Some(backlight) = &mut self.backlight {
+            backlight
+                .set_level(self.backlight_level)
+                .map_err(SleepError::Backlight)?;
+        }
         Ok(())
     }
 
@@ -380,16 +762,44 @@ where
     }
 }
 
+/// Error returned by [`Display::sleep`], [`Display::wake`] and their
+/// non-blocking variants.
+#[derive(Debug)]
+pub enum SleepError<DI, BL> {
+    /// Error caused by the display interface.
+    Interface(DI),
+    /// Error caused by the backlight.
+    Backlight(BL),
+    /// [`Display::sleep`] was called while the display was already sleeping.
+    AlreadySleeping,
+    /// [`Display::wake`] was called while the display wasn't sleeping.
+    NotSleeping,
+    /// A transition started by [`Display::start_sleep`] or
+    /// [`Display::start_wake`] wasn't completed by
+    /// [`Display::poll_transition`].
+    Busy,
+    /// The display was powered off using [`Display::power_off`].
+    PoweredOff,
+}
+
 /// Mock implementations of embedded-hal and interface traits.
 ///
 /// Do not use types in this module outside of doc tests.
 #[doc(hidden)]
 pub mod _mock {
-    use core::convert::Infallible;
+    use core::{
+        cell::{Cell, RefCell},
+        convert::Infallible,
+    };
 
     use embedded_hal::{delay::DelayNs, digital, spi};
 
-    use crate::{interface::Interface, models::ILI9341Rgb565, Builder, Display, NoResetPin};
+    use crate::{
+        backlight::Backlight,
+        interface::{Interface, ReadInterface},
+        models::ILI9341Rgb565,
+        Builder, Display, NoResetPin,
+    };
 
     pub fn new_mock_display() -> Display<MockDisplayInterface, ILI9341Rgb565, NoResetPin> {
         Builder::new(ILI9341Rgb565, MockDisplayInterface)
@@ -413,6 +823,43 @@ pub mod _mock {
         type Error = core::convert::Infallible;
     }
 
+    /// Input pin which toggles its level every time it's read.
+    #[derive(Default)]
+    pub struct MockInputPin {
+        pub high: bool,
+    }
+
+    impl digital::InputPin for MockInputPin {
+        fn is_high(&mut self) -> Result<bool, Self::Error> {
+            self.high = !self.high;
+            Ok(self.high)
+        }
+
+        fn is_low(&mut self) -> Result<bool, Self::Error> {
+            self.is_high().map(|high| !high)
+        }
+    }
+
+    impl digital::ErrorType for MockInputPin {
+        type Error = core::convert::Infallible;
+    }
+
+    pub struct MockPwm;
+
+    impl embedded_hal::pwm::SetDutyCycle for MockPwm {
+        fn max_duty_cycle(&self) -> u16 {
+            u16::MAX
+        }
+
+        fn set_duty_cycle(&mut self, _duty: u16) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    impl embedded_hal::pwm::ErrorType for MockPwm {
+        type Error = core::convert::Infallible;
+    }
+
     pub struct MockSpi;
 
     impl spi::SpiDevice for MockSpi {
@@ -459,4 +906,153 @@ pub mod _mock {
             Ok(())
         }
     }
+
+    /// Backlight which stores the last level.
+    pub struct MockBacklight<'a>(pub &'a Cell<u8>);
+
+    impl Backlight for MockBacklight<'_> {
+        type Error = Infallible;
+
+        fn set_level(&mut self, level: u8) -> Result<(), Self::Error> {
+            self.0.set(level);
+            Ok(())
+        }
+    }
+
+    /// Width and height of the framebuffer stored by [`MockInterfaceState`].
+    pub const MOCK_FRAMEBUFFER_SIZE: usize = 16;
+
+    /// State which is shared between a [`MockInterface`] and a test.
+    pub struct MockInterfaceState {
+        /// Number of sent commands.
+        pub commands: usize,
+        /// First four parameter bytes of the last sent instance of each command.
+        pub params: [[u8; 4]; 256],
+        /// First four response bytes returned by read commands, indexed by the
+        /// command.
+        pub read_values: [[u8; 4]; 256],
+        /// Sending commands returns an error if this is set.
+        pub fail_commands: bool,
+        /// Sending pixels returns an error if this is set.
+        pub fail_pixels: bool,
+        /// Pixels written to the top left corner of the framebuffer.
+        pub framebuffer: [[u16; MOCK_FRAMEBUFFER_SIZE]; MOCK_FRAMEBUFFER_SIZE],
+        cursor: (u16, u16),
+    }
+
+    impl MockInterfaceState {
+        pub const fn new() -> Self {
+            Self {
+                commands: 0,
+                params: [[0; 4]; 256],
+                read_values: [[0; 4]; 256],
+                fail_commands: false,
+                fail_pixels: false,
+                framebuffer: [[0; MOCK_FRAMEBUFFER_SIZE]; MOCK_FRAMEBUFFER_SIZE],
+                cursor: (0, 0),
+            }
+        }
+
+        /// Returns the first two parameters of the last sent instance of
+        /// `command` as big endian `u16` values.
+        pub fn params_u16(&self, command: u8) -> (u16, u16) {
+            let params = self.params[usize::from(command)];
+            (
+                u16::from_be_bytes([params[0], params[1]]),
+                u16::from_be_bytes([params[2], params[3]]),
+            )
+        }
+
+        fn write_pixel(&mut self, value: u16) {
+            let (x, y) = self.cursor;
+            if let Some(pixel) = self
+                .framebuffer
+                .get_mut(usize::from(y))
+                .and_then(|row| row.get_mut(usize::from(x)))
+            {
+                *pixel = value;
+            }
+
+            let (start, end) = self.params_u16(0x2A);
+            self.cursor = if x >= end { (start, y + 1) } else { (x + 1, y) };
+        }
+    }
+
+    impl Default for MockInterfaceState {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    /// Error returned by [`MockInterface`].
+    #[derive(Debug, PartialEq, Eq)]
+    pub struct MockInterfaceError;
+
+    /// Interface which records the sent commands and pixels in a shared
+    /// [`MockInterfaceState`].
+    pub struct MockInterface<'a>(pub &'a RefCell<MockInterfaceState>);
+
+    impl Interface for MockInterface<'_> {
+        type Word = u8;
+        type Error = MockInterfaceError;
+
+        fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+            let mut state = self.0.borrow_mut();
+            if state.fail_commands {
+                return Err(MockInterfaceError);
+            }
+            state.commands += 1;
+
+            let mut params = [0; 4];
+            let len = args.len().min(4);
+            params[..len].copy_from_slice(&args[..len]);
+            state.params[usize::from(command)] = params;
+
+            if command == 0x2C {
+                state.cursor = (state.params_u16(0x2A).0, state.params_u16(0x2B).0);
+            }
+
+            Ok(())
+        }
+
+        fn send_pixels<const N: usize>(
+            &mut self,
+            pixels: impl IntoIterator<Item = [Self::Word; N]>,
+        ) -> Result<(), Self::Error> {
+            let mut state = self.0.borrow_mut();
+            if state.fail_pixels {
+                return Err(MockInterfaceError);
+            }
+            for pixel in pixels {
+                // RGB565 pixels are stored as big endian values and other
+                // formats as their first word
+                let value = match pixel[..] {
+                    [high, low] => u16::from_be_bytes([high, low]),
+                    _ => u16::from(pixel[0]),
+                };
+                state.write_pixel(value);
+            }
+
+            Ok(())
+        }
+
+        fn send_repeated_pixel<const N: usize>(
+            &mut self,
+            pixel: [Self::Word; N],
+            count: u32,
+        ) -> Result<(), Self::Error> {
+            self.send_pixels(core::iter::repeat(pixel).take(count as usize))
+        }
+    }
+
+    impl ReadInterface for MockInterface<'_> {
+        fn read_command(&mut self, command: u8, data: &mut [u8]) -> Result<(), Self::Error> {
+            data.fill(0);
+            let values = self.0.borrow().read_values[usize::from(command)];
+            let len = data.len().min(4);
+            data[..len].copy_from_slice(&values[..len]);
+
+            Ok(())
+        }
+    }
 }
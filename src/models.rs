@@ -34,6 +34,28 @@ pub trait Model {
     /// The framebuffer size in pixels.
     const FRAMEBUFFER_SIZE: (u16, u16);
 
+    /// Whether the display brightness can be controlled using the Write Display
+    /// Brightness (0x51), Write CTRL Display (0x53) and Write CABC (0x55) commands.
+    ///
+    /// Defaults to `false`, because on most displays with a separate backlight
+    /// these commands only control an output pin, which is often not connected.
+    const BRIGHTNESS_CONTROL: bool = false;
+
+    /// Whether the display controller supports the 3 bits per pixel format
+    /// ([`BitsPerPixel::Three`](crate::dcs::BitsPerPixel::Three)) on the
+    /// interface used to connect the display.
+    ///
+    /// Defaults to `false`, because most controllers only support this format
+    /// on some interfaces, if at all.
+    const PIXEL_FORMAT_3BPP: bool = false;
+
+    /// Minimum time in microseconds to wait after the Enter Sleep Mode (0x10)
+    /// or Exit Sleep Mode (0x11) command was sent, before the other command
+    /// can be sent.
+    ///
+    /// Defaults to 120 ms, which is required by all supported models.
+    const SLEEP_DELAY_US: u32 = 120_000;
+
     /// Initializes the display for this model with MADCTL from [crate::Display]
     /// and returns the value of MADCTL set by init
     fn init<DELAY, DI>(
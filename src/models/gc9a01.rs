@@ -13,6 +13,9 @@ use crate::{
 use super::Model;
 
 /// GC9A01 display in Rgb565 color mode.
+///
+/// Round GC9A01 panels should use [`VisibleArea::Circle`](crate::options::VisibleArea::Circle)
+/// to avoid sending pixels for the invisible corners.
 pub struct GC9A01;
 
 impl Model for GC9A01 {
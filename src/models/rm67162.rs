@@ -4,10 +4,10 @@ use embedded_hal::delay::DelayNs;
 use crate::{
     dcs::{
         BitsPerPixel, ExitSleepMode, InterfaceExt, PixelFormat, SetAddressMode, SetDisplayOn,
-        SetInvertMode, SetPixelFormat,
+        SetInvertMode, SetPixelFormat, WriteCtrlDisplay, WriteDisplayBrightness,
     },
     interface::Interface,
-    options::ModelOptions,
+    options::{BrightnessControl, ModelOptions},
 };
 
 use super::Model;
@@ -30,6 +30,7 @@ pub struct RM67162;
 impl Model for RM67162 {
     type ColorFormat = Rgb565;
     const FRAMEBUFFER_SIZE: (u16, u16) = (240, 536);
+    const BRIGHTNESS_CONTROL: bool = true;
 
     fn init<DELAY, DI>(
         &mut self,
@@ -54,8 +55,8 @@ impl Model for RM67162 {
         di.write_raw(0xFE, &[0x0A])?;
         di.write_raw(0x29, &[0x10])?;
         di.write_raw(0xFE, &[0x00])?;
-        di.write_raw(0x51, &[0xaf])?; // Set brightness
-        di.write_raw(0x53, &[0x20])?;
+        di.write_command(WriteDisplayBrightness::new(0xaf))?;
+        di.write_command(WriteCtrlDisplay::new(BrightnessControl::default()))?;
         di.write_raw(0x35, &[0x00])?;
 
         let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Self::ColorFormat>());
@@ -0,0 +1,431 @@
+use core::{cell::Cell, task::Poll};
+
+use embedded_hal::{delay::DelayNs, digital::OutputPin};
+
+use crate::{
+    backlight::Backlight,
+    dcs::{self, InterfaceExt},
+    interface::{Interface, InterfacePixelFormat},
+    models::Model,
+    Display, InitError, SleepError,
+};
+
+/// Non-blocking display initialization.
+///
+/// Created by [`Builder::init_nonblocking`](crate::Builder::init_nonblocking).
+///
+/// The initialization sequence of the model is performed in steps, which are
+/// separated by the delays required by the display controller. Every call to
+/// [`poll`](Self::poll) performs all steps whose deadline has passed and
+/// returns the initialized [`Display`] after the last step.
+///
+/// The steps are executed by replaying [`Model::init`] and only sending the
+/// commands of the current step to the display, which requires the init
+/// sequence of the model to be deterministic.
+pub struct InitSequence<DI, M, RST, BL, PWR>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+    PWR: OutputPin,
+{
+    display: Option<Display<DI, M, RST, BL, PWR>>,
+    step: u32,
+    ready_at: u64,
+}
+
+impl<DI, M, RST, BL, PWR> InitSequence<DI, M, RST, BL, PWR>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+    PWR: OutputPin,
+{
+    pub(crate) fn new(display: Display<DI, M, RST, BL, PWR>) -> Self {
+        Self {
+            display: Some(display),
+            step: 0,
+            ready_at: 0,
+        }
+    }
+
+    /// Advances the initialization.
+    ///
+    /// `now_us` is the current time in microseconds of a monotonic clock,
+    /// which must be the same clock for all calls. Returns the initialized
+    /// display once all steps are completed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this method is called after it returned [`Poll::Ready`].
+    #[allow(clippy::type_complexity)]
+    pub fn poll(
+        &mut self,
+        now_us: u64,
+    ) -> Poll<
+        Result<
+            Display<DI, M, RST, BL, PWR>,
+            InitError<DI::Error, RST::Error, BL::Error, PWR::Error>,
+        >,
+    > {
+        let display = self
+            .display
+            .as_mut()
+            .expect("init sequence polled after completion");
+
+        while now_us >= self.ready_at {
+            match display.init_step(self.step) {
+                Ok(Some(wait_us)) => {
+                    self.step += 1;
+                    self.ready_at = now_us + u64::from(wait_us);
+                }
+                Ok(None) => return Poll::Ready(Ok(self.display.take().unwrap())),
+                Err(error) => {
+                    self.display = None;
+                    return Poll::Ready(Err(error));
+                }
+            }
+        }
+
+        Poll::Pending
+    }
+
+    /// Returns the time in microseconds at which the next step is ready to
+    /// be performed.
+    pub fn ready_at(&self) -> u64 {
+        self.ready_at
+    }
+}
+
+impl<DI, M, RST, BL, PWR> Display<DI, M, RST, BL, PWR>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+    PWR: OutputPin,
+{
+    /// Performs a single step of the initialization.
+    ///
+    /// Returns the time in microseconds to wait before the next step, or
+    /// `None` after the last step.
+    fn init_step(
+        &mut self,
+        step: u32,
+    ) -> Result<Option<u32>, InitError<DI::Error, RST::Error, BL::Error, PWR::Error>> {
+        match step {
+            0 => {
+                if let Some(backlight) = &mut self.backlight {
+                    backlight.set_level(0).map_err(InitError::Backlight)?;
+                }
+
+                match &mut self.power {
+                    Some(power) => {
+                        power.set_high().map_err(InitError::PowerPin)?;
+                        Ok(Some(self.power_settle_us))
+                    }
+                    None => Ok(Some(0)),
+                }
+            }
+            1 => match &mut self.rst {
+                Some(rst) => {
+                    rst.set_low().map_err(InitError::ResetPin)?;
+                    Ok(Some(10))
+                }
+                None => {
+                    self.di
+                        .write_command(dcs::SoftReset)
+                        .map_err(InitError::Interface)?;
+                    Ok(Some(0))
+                }
+            },
+            2 => {
+                if let Some(rst) = &mut self.rst {
+                    rst.set_high().map_err(InitError::ResetPin)?;
+                }
+                Ok(Some(0))
+            }
+            _ => {
+                let position = Cell::new(0);
+                let mut di = ReplayInterface {
+                    di: &mut self.di,
+                    position: &position,
+                    target: step - 3,
+                };
+                let mut delay = ReplayDelay {
+                    position: &position,
+                    target: step - 3,
+                    wait_us: None,
+                };
+
+                let madctl = self
+                    .model
+                    .init(&mut di, &mut delay, &self.options)
+                    .map_err(InitError::Interface)?;
+
+                if delay.wait_us.is_none() {
+                    self.finish_init(madctl);
+                }
+
+                Ok(delay.wait_us)
+            }
+        }
+    }
+
+    ///
+    /// Puts the display to sleep without blocking.
+    ///
+    /// Works like [Self::sleep], but returns immediately after the command was
+    /// sent. The transition must be completed by calling
+    /// [Self::poll_transition] before the display can be woken up again.
+    ///
+    /// `now_us` is the current time in microseconds of a monotonic clock,
+    /// which must be the same clock for all non-blocking methods.
+    ///
+    pub fn start_sleep(&mut self, now_us: u64) -> Result<(), SleepError<DI::Error, BL::Error>> {
+        if !self.state.powered {
+            return Err(SleepError::PoweredOff);
+        }
+        if self.pending.is_some() {
+            return Err(SleepError::Busy);
+        }
+        if self.state.sleeping {
+            return Err(SleepError::AlreadySleeping);
+        }
+        self.di
+            .write_command(dcs::EnterSleepMode)
+            .map_err(SleepError::Interface)?;
+        self.state.sleeping = true;
+        self.pending = Some(PendingTransition {
+            ready_at: now_us + u64::from(M::SLEEP_DELAY_US),
+            wake: false,
+        });
+        if let Some(backlight) = &mut self.backlight {
+            backlight.set_level(0).map_err(SleepError::Backlight)?;
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Wakes the display without blocking.
+    ///
+    /// Works like [Self::wake], but returns immediately after the command was
+    /// sent. The backlight is restored by [Self::poll_transition] once the
+    /// display has woken up.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// # let mut now_us = 0;
+    /// display.start_sleep(now_us).unwrap();
+    /// while display.poll_transition(now_us).is_pending() {
+    ///     // do other work
+    /// #   now_us += 10_000;
+    /// }
+    ///
+    /// display.start_wake(now_us).unwrap();
+    /// while display.poll_transition(now_us).is_pending() {
+    ///     // do other work
+    /// #   now_us += 10_000;
+    /// }
+    /// ```
+    pub fn start_wake(&mut self, now_us: u64) -> Result<(), SleepError<DI::Error, BL::Error>> {
+        if !self.state.powered {
+            return Err(SleepError::PoweredOff);
+        }
+        if self.pending.is_some() {
+            return Err(SleepError::Busy);
+        }
+        if !self.state.sleeping {
+            return Err(SleepError::NotSleeping);
+        }
+        self.di
+            .write_command(dcs::ExitSleepMode)
+            .map_err(SleepError::Interface)?;
+        self.state.sleeping = false;
+        self.pending = Some(PendingTransition {
+            ready_at: now_us + u64::from(M::SLEEP_DELAY_US),
+            wake: true,
+        });
+
+        Ok(())
+    }
+
+    ///
+    /// Completes a transition started by [Self::start_sleep] or
+    /// [Self::start_wake].
+    ///
+    /// Returns [Poll::Ready] once the required delay has passed, or if no
+    /// transition is pending.
+    ///
+    pub fn poll_transition(&mut self, now_us: u64) -> Poll<Result<(), BL::Error>> {
+        match self.pending {
+            Some(pending) if now_us < pending.ready_at => Poll::Pending,
+            Some(pending) => {
+                self.pending = None;
+                match &mut self.backlight {
+                    Some(backlight) if pending.wake => {
+                        Poll::Ready(backlight.set_level(self.backlight_level))
+                    }
+                    _ => Poll::Ready(Ok(())),
+                }
+            }
+            None => Poll::Ready(Ok(())),
+        }
+    }
+
+    ///
+    /// Returns the time in microseconds at which the pending sleep or wake
+    /// transition is completed, or `None` if no transition is pending.
+    ///
+    pub fn transition_ready_at(&self) -> Option<u64> {
+        self.pending.map(|pending| pending.ready_at)
+    }
+}
+
+/// Sleep or wake transition started by a non-blocking method.
+#[derive(Clone, Copy)]
+pub(crate) struct PendingTransition {
+    ready_at: u64,
+    wake: bool,
+}
+
+/// Interface which only forwards the commands of a single init step.
+///
+/// The steps are separated by the delays in the init sequence, which are
+/// counted by [`ReplayDelay`].
+struct ReplayInterface<'a, DI> {
+    di: &'a mut DI,
+    position: &'a Cell<u32>,
+    target: u32,
+}
+
+impl<DI: Interface> ReplayInterface<'_, DI> {
+    fn is_active(&self) -> bool {
+        self.position.get() == self.target
+    }
+}
+
+impl<DI: Interface> Interface for ReplayInterface<'_, DI> {
+    type Word = DI::Word;
+    type Error = DI::Error;
+
+    fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        if self.is_active() {
+            self.di.send_command(command, args)?;
+        }
+        Ok(())
+    }
+
+    fn send_pixels<const N: usize>(
+        &mut self,
+        pixels: impl IntoIterator<Item = [Self::Word; N]>,
+    ) -> Result<(), Self::Error> {
+        if self.is_active() {
+            self.di.send_pixels(pixels)?;
+        }
+        Ok(())
+    }
+
+    fn send_repeated_pixel<const N: usize>(
+        &mut self,
+        pixel: [Self::Word; N],
+        count: u32,
+    ) -> Result<(), Self::Error> {
+        if self.is_active() {
+            self.di.send_repeated_pixel(pixel, count)?;
+        }
+        Ok(())
+    }
+}
+
+/// Delay which records the delay at the end of a single init step.
+struct ReplayDelay<'a> {
+    position: &'a Cell<u32>,
+    target: u32,
+    wait_us: Option<u32>,
+}
+
+impl ReplayDelay<'_> {
+    fn wait(&mut self, us: u32) {
+        if self.position.get() == self.target {
+            self.wait_us = Some(us);
+        }
+        self.position.set(self.position.get() + 1);
+    }
+}
+
+impl DelayNs for ReplayDelay<'_> {
+    fn delay_ns(&mut self, ns: u32) {
+        self.wait(ns.div_ceil(1_000));
+    }
+
+    fn delay_us(&mut self, us: u32) {
+        self.wait(us);
+    }
+
+    fn delay_ms(&mut self, ms: u32) {
+        self.wait(ms.saturating_mul(1_000));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cell::RefCell;
+
+    use super::*;
+    use crate::{
+        _mock::{MockInterface, MockInterfaceState},
+        models::ILI9341Rgb565,
+        Builder,
+    };
+
+    #[test]
+    fn init_is_split_into_steps() {
+        let blocking = RefCell::new(MockInterfaceState::new());
+        Builder::new(ILI9341Rgb565, MockInterface(&blocking))
+            .init(&mut crate::_mock::MockDelay)
+            .unwrap();
+
+        let state = RefCell::new(MockInterfaceState::new());
+        let mut init = Builder::new(ILI9341Rgb565, MockInterface(&state)).init_nonblocking();
+        let commands = || state.borrow().commands;
+
+        // soft reset and commands before the first delay
+        assert!(init.poll(0).is_pending());
+        assert_eq!(commands(), 1);
+        assert_eq!(init.ready_at(), 5_000);
+
+        assert!(init.poll(4_999).is_pending());
+        assert_eq!(commands(), 1);
+
+        assert!(init.poll(5_000).is_pending());
+        assert!(init.poll(125_000).is_pending());
+        assert!(init.poll(265_000).is_ready());
+        assert_eq!(commands(), blocking.borrow().commands);
+    }
+
+    #[test]
+    fn sleep_transition_is_pollable() {
+        let mut display = crate::_mock::new_mock_display();
+
+        display.start_sleep(1_000).unwrap();
+        assert!(display.is_sleeping());
+        assert_eq!(display.transition_ready_at(), Some(121_000));
+        assert!(matches!(display.start_wake(2_000), Err(SleepError::Busy)));
+
+        assert!(display.poll_transition(120_999).is_pending());
+        assert!(display.poll_transition(121_000).is_ready());
+        assert_eq!(display.transition_ready_at(), None);
+
+        display.start_wake(121_000).unwrap();
+        assert!(!display.is_sleeping());
+    }
+}
@@ -1,5 +1,7 @@
 //! [ModelOptions] and other helper types.
 
+use embedded_graphics_core::geometry::Size;
+
 use crate::models::Model;
 
 mod orientation;
@@ -23,6 +25,8 @@ pub struct ModelOptions {
     pub display_size: (u16, u16),
     /// Display offset (x, y) for given display.
     pub display_offset: (u16, u16),
+    /// Visible area of non rectangular displays.
+    pub visible_area: VisibleArea,
 }
 
 impl ModelOptions {
@@ -35,6 +39,7 @@ impl ModelOptions {
             refresh_order: RefreshOrder::default(),
             display_size: M::FRAMEBUFFER_SIZE,
             display_offset: (0, 0),
+            visible_area: VisibleArea::default(),
         }
     }
 
@@ -47,6 +52,7 @@ impl ModelOptions {
             refresh_order: RefreshOrder::default(),
             display_size,
             display_offset,
+            visible_area: VisibleArea::default(),
         }
     }
 
@@ -179,6 +185,47 @@ pub enum TearingEffect {
     HorizontalAndVertical,
 }
 
+/// Content adaptive brightness control (CABC) mode.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum CabcMode {
+    /// Content adaptive brightness control is disabled.
+    #[default]
+    Off,
+    /// Optimized for user interfaces.
+    UserInterface,
+    /// Optimized for still pictures.
+    StillPicture,
+    /// Optimized for moving images.
+    MovingImage,
+}
+
+/// Brightness control settings.
+///
+/// Defaults to brightness control enabled with dimming and backlight control
+/// disabled.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct BrightnessControl {
+    /// Enables the brightness control block.
+    ///
+    /// The display brightness value is ignored if the brightness control is
+    /// disabled.
+    pub brightness_control: bool,
+    /// Enables smooth transitions when the brightness is changed.
+    pub dimming: bool,
+    /// Enables the backlight control output.
+    pub backlight: bool,
+}
+
+impl Default for BrightnessControl {
+    fn default() -> Self {
+        Self {
+            brightness_control: true,
+            dimming: false,
+            backlight: false,
+        }
+    }
+}
+
 /// Subpixel order.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ColorOrder {
@@ -193,3 +240,148 @@ impl Default for ColorOrder {
         Self::Rgb
     }
 }
+
+/// Scroll axis, relative to the current display orientation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScrollAxis {
+    /// Vertical scrolling.
+    Vertical,
+    /// Horizontal scrolling.
+    Horizontal,
+}
+
+/// Number of bits used to store a palette index in indexed color images.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexBits {
+    /// 1 bit per pixel, 2 color palette.
+    One,
+    /// 2 bits per pixel, 4 color palette.
+    Two,
+    /// 4 bits per pixel, 16 color palette.
+    Four,
+    /// 8 bits per pixel, 256 color palette.
+    Eight,
+}
+
+impl IndexBits {
+    /// Returns the number of bits per pixel.
+    pub const fn bits(self) -> u8 {
+        match self {
+            Self::One => 1,
+            Self::Two => 2,
+            Self::Four => 4,
+            Self::Eight => 8,
+        }
+    }
+}
+
+/// Visible area of the display.
+///
+/// Some panels, like round `GC9A01` displays, only show a part of the
+/// rectangular framebuffer. Drawing operations are trimmed to the visible
+/// area, which avoids sending pixels which can't be seen.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VisibleArea {
+    /// The whole display area is visible.
+    #[default]
+    Full,
+    /// A circle, which touches the edges of the display area, is visible.
+    Circle,
+    /// Custom visible spans.
+    ///
+    /// Each entry contains the first and last (inclusive) visible column of a
+    /// row, starting at the top row. Rows without an entry aren't visible.
+    /// The spans use the coordinates of the current display orientation.
+    Spans(&'static [(u16, u16)]),
+}
+
+impl VisibleArea {
+    /// Returns the first and last visible column in row `y` of a display with the given `size`.
+    ///
+    /// Returns `None` if no pixel in the row is visible.
+    pub(crate) fn columns(&self, size: Size, y: u32) -> Option<(u32, u32)> {
+        if y >= size.height || size.width == 0 {
+            return None;
+        }
+
+        match self {
+            Self::Full => Some((0, size.width - 1)),
+            Self::Circle => {
+                // Doubled coordinates are used to get the pixel centers
+                // without fractions
+                let diameter = size.width.min(size.height) as i64;
+                let dy = 2 * y as i64 + 1 - size.height as i64;
+                let remaining = diameter * diameter - dy * dy;
+                if remaining < 0 {
+                    return None;
+                }
+
+                let half_width = isqrt(remaining as u64) as i64;
+                let center = size.width as i64 - 1;
+                let start = (center - half_width + 1).div_euclid(2).max(0);
+                let end = (center + half_width)
+                    .div_euclid(2)
+                    .min(size.width as i64 - 1);
+
+                (start <= end).then_some((start as u32, end as u32))
+            }
+            Self::Spans(spans) => {
+                let &(start, end) = spans.get(y as usize)?;
+                let end = u32::from(end).min(size.width - 1);
+
+                (u32::from(start) <= end).then_some((u32::from(start), end))
+            }
+        }
+    }
+}
+
+/// Integer square root.
+fn isqrt(value: u64) -> u64 {
+    let mut low = 0;
+    let mut high = value.min(u64::from(u32::MAX)) + 1;
+
+    while high - low > 1 {
+        let mid = low + (high - low) / 2;
+        if mid * mid <= value {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+
+    low
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn circle_columns() {
+        let size = Size::new(4, 4);
+        let area = VisibleArea::Circle;
+
+        assert_eq!(area.columns(size, 0), Some((1, 2)));
+        assert_eq!(area.columns(size, 1), Some((0, 3)));
+        assert_eq!(area.columns(size, 2), Some((0, 3)));
+        assert_eq!(area.columns(size, 3), Some((1, 2)));
+        assert_eq!(area.columns(size, 4), None);
+
+        let size = Size::new(240, 240);
+        assert_eq!(area.columns(size, 0), Some((109, 130)));
+        assert_eq!(area.columns(size, 120), Some((0, 239)));
+        assert_eq!(area.columns(size, 239), Some((109, 130)));
+    }
+
+    #[test]
+    fn span_columns() {
+        let area = VisibleArea::Spans(&[(2, 5), (0, 100), (4, 3)]);
+        let size = Size::new(10, 10);
+
+        assert_eq!(area.columns(size, 0), Some((2, 5)));
+        assert_eq!(area.columns(size, 1), Some((0, 9)));
+        assert_eq!(area.columns(size, 2), None);
+        assert_eq!(area.columns(size, 3), None);
+        assert_eq!(VisibleArea::Full.columns(size, 9), Some((0, 9)));
+    }
+}
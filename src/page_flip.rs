@@ -0,0 +1,218 @@
+use embedded_hal::digital::OutputPin;
+
+use crate::{
+    backlight::Backlight,
+    interface::{Interface, InterfacePixelFormat},
+    models::Model,
+    Display,
+};
+
+/// Error returned by [`Display::enable_page_flipping`].
+#[derive(Debug)]
+pub enum PageFlipError<DI> {
+    /// Error caused by the display interface.
+    Interface(DI),
+    /// The framebuffer of the display controller can't fit two pages.
+    ///
+    /// Page flipping requires the framebuffer to be at least twice as high as
+    /// the display in the default orientation.
+    InsufficientMemory,
+}
+
+impl<DI, M, RST, BL, PWR> Display<DI, M, RST, BL, PWR>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+    PWR: OutputPin,
+{
+    /// Enables page flipping.
+    ///
+    /// Page flipping uses the part of the controller framebuffer which isn't
+    /// covered by the display as a second page. All drawing operations are
+    /// sent to the off-screen page, which can then be shown instantly by calling
+    /// [`flip_pages`](Self::flip_pages). Switching between the pages is done by
+    /// using the vertical scrolling commands (VSCRDEF and VSCRSAD), which means
+    /// that scrolling can't be used while page flipping is enabled. The
+    /// orientation shouldn't be changed while page flipping is enabled.
+    ///
+    /// The visible page isn't initialized by this method and the whole frame
+    /// should be drawn before the pages are flipped for the first time.
+    ///
+    /// Returns [`PageFlipError::InsufficientMemory`] if the framebuffer isn't
+    /// large enough to store two pages, e.g. for a 240x135 display on an
+    /// ST7789 with a 240x320 framebuffer page flipping is supported, but not
+    /// for a 240x240 display.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use embedded_graphics::{pixelcolor::Rgb565, prelude::*};
+    /// use mipidsi::{Builder, models::ILI9341Rgb565};
+    ///
+    /// # let di = mipidsi::_mock::MockDisplayInterface;
+    /// # let mut delay = mipidsi::_mock::MockDelay;
+    /// let mut display = Builder::new(ILI9341Rgb565, di)
+    ///     .display_size(240, 160)
+    ///     .init(&mut delay)
+    ///     .unwrap();
+    ///
+    /// display.enable_page_flipping().unwrap();
+    ///
+    /// // Draw the next frame off-screen and show it
+    /// display.clear(Rgb565::BLUE).unwrap();
+    /// display.flip_pages().unwrap();
+    /// ```
+    pub fn enable_page_flipping(&mut self) -> Result<(), PageFlipError<DI::Error>> {
+        let height = self.options.display_size.1;
+        if u32::from(height) * 2 > u32::from(M::FRAMEBUFFER_SIZE.1) {
+            return Err(PageFlipError::InsufficientMemory);
+        }
+
+        // The whole framebuffer is used as the scroll region
+        self.set_vertical_scroll_region(0, 0)
+            .map_err(PageFlipError::Interface)?;
+
+        self.show_page(0).map_err(PageFlipError::Interface)?;
+        self.draw_page = Some(height);
+
+        Ok(())
+    }
+
+    /// Disables page flipping.
+    ///
+    /// Drawing operations are sent to the regular display area again. The
+    /// display content should be redrawn after page flipping was disabled.
+    pub fn disable_page_flipping(&mut self) -> Result<(), DI::Error> {
+        if self.draw_page.take().is_some() {
+            self.set_vertical_scroll_offset(0)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if page flipping is enabled.
+    pub fn is_page_flipping_enabled(&self) -> bool {
+        self.draw_page.is_some()
+    }
+
+    /// Shows the off-screen page.
+    ///
+    /// The previously visible page becomes the new off-screen page. Subsequent
+    /// drawing operations don't affect the visible content until the pages are
+    /// flipped again. The content of the new off-screen page is the content of
+    /// the frame before the previous flip.
+    ///
+    /// Does nothing if page flipping isn't enabled.
+    pub fn flip_pages(&mut self) -> Result<(), DI::Error> {
+        let Some(draw_page) = self.draw_page else {
+            return Ok(());
+        };
+
+        self.show_page(draw_page)?;
+        self.draw_page = Some(self.options.display_size.1 - draw_page);
+
+        Ok(())
+    }
+
+    /// Scrolls the display to show the page which starts at `row`.
+    ///
+    /// `row` is a native framebuffer row, like the scroll start address and
+    /// `draw_page`. Reversed row orders are handled by the address window
+    /// offset when drawing to a page.
+    fn show_page(&mut self, row: u16) -> Result<(), DI::Error> {
+        let rows = M::FRAMEBUFFER_SIZE.1;
+        let offset = self.options.display_offset.1;
+
+        self.set_vertical_scroll_offset((row + rows - offset) % rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cell::RefCell;
+
+    use embedded_graphics_core::{pixelcolor::Rgb565, prelude::*};
+
+    use super::*;
+    use crate::{
+        _mock::{MockDelay, MockDisplayInterface, MockInterface, MockInterfaceState},
+        models::ILI9341Rgb565,
+        options::{Orientation, Rotation},
+        Builder,
+    };
+
+    #[test]
+    fn flips_between_pages() {
+        let mut display = Builder::new(ILI9341Rgb565, MockDisplayInterface)
+            .display_size(240, 160)
+            .init(&mut MockDelay)
+            .unwrap();
+
+        display.enable_page_flipping().unwrap();
+        assert_eq!(display.address_offset(), (0, 160));
+
+        display.flip_pages().unwrap();
+        assert_eq!(display.address_offset(), (0, 0));
+
+        display.disable_page_flipping().unwrap();
+        assert!(!display.is_page_flipping_enabled());
+        assert_eq!(display.address_offset(), (0, 0));
+    }
+
+    #[test]
+    fn reversed_rows() {
+        let mut display = Builder::new(ILI9341Rgb565, MockDisplayInterface)
+            .display_size(240, 160)
+            .orientation(Orientation::new().rotate(Rotation::Deg180))
+            .init(&mut MockDelay)
+            .unwrap();
+
+        // Rows are counted from the bottom of the framebuffer in 180° rotation
+        display.enable_page_flipping().unwrap();
+        assert_eq!(display.address_offset(), (0, 0));
+        assert_eq!(display.draw_page, Some(160));
+    }
+
+    #[test]
+    fn reversed_rows_draw_to_hidden_page() {
+        let state = RefCell::new(MockInterfaceState::new());
+        let mut display = Builder::new(ILI9341Rgb565, MockInterface(&state))
+            .display_size(240, 160)
+            .orientation(Orientation::new().rotate(Rotation::Deg180))
+            .init(&mut MockDelay)
+            .unwrap();
+
+        // Returns the first native row shown by the display and the native
+        // rows of the last address window, which are reversed in 180° rotation
+        let pages = || {
+            let state = state.borrow();
+            let (start, end) = state.params_u16(0x2B);
+            (state.params_u16(0x37).0, (319 - end, 319 - start))
+        };
+
+        display.enable_page_flipping().unwrap();
+        display.clear(Rgb565::RED).unwrap();
+        assert_eq!(pages(), (0, (160, 319)));
+
+        display.flip_pages().unwrap();
+        display.clear(Rgb565::BLUE).unwrap();
+        assert_eq!(pages(), (160, (0, 159)));
+    }
+
+    #[test]
+    fn requires_two_pages() {
+        let mut display = Builder::new(ILI9341Rgb565, MockDisplayInterface)
+            .display_size(240, 240)
+            .init(&mut MockDelay)
+            .unwrap();
+
+        assert!(matches!(
+            display.enable_page_flipping(),
+            Err(PageFlipError::InsufficientMemory)
+        ));
+        assert!(!display.is_page_flipping_enabled());
+    }
+}
@@ -0,0 +1,219 @@
+use core::ops::Range;
+
+use embedded_hal::digital::OutputPin;
+
+use crate::{
+    backlight::Backlight,
+    dcs::{self, InterfaceExt},
+    interface::{Interface, InterfacePixelFormat},
+    models::Model,
+    options::MemoryMapping,
+    Display, StateError,
+};
+
+/// Partial area in native rows or columns, with inclusive start and end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PartialArea {
+    /// Set using the Set Partial Rows (0x30) command.
+    Rows(u16, u16),
+    /// Set using the Set Partial Columns (0x31) command.
+    Columns(u16, u16),
+}
+
+/// Error returned by [`Display::set_partial_area`].
+#[derive(Debug)]
+pub enum PartialAreaError<DI> {
+    /// Error caused by the display interface.
+    Interface(DI),
+    /// The partial area doesn't contain any rows of the display.
+    EmptyArea,
+    /// The display was powered off using [`Display::power_off`].
+    PoweredOff,
+    /// The display is sleeping.
+    Sleeping,
+}
+
+impl<DI, M, RST, BL, PWR> Display<DI, M, RST, BL, PWR>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+    PWR: OutputPin,
+{
+    /// Enters partial mode and sets the partial display area.
+    ///
+    /// In partial mode only the given `rows` of the current orientation are
+    /// refreshed by the display, while the rest of the display is turned off or
+    /// shows a background color, depending on the display controller. This can
+    /// be used to reduce the power consumption if only a part of the display
+    /// shows content, e.g. for an always on watch face.
+    ///
+    /// The rows are translated into the native rows of the display, taking the
+    /// current orientation and display offset into account. In 90° and 270°
+    /// rotated orientations the rows of the current orientation are columns
+    /// in the default orientation and the partial area is set using the Set
+    /// Partial Columns (0x31) command, which isn't supported by all display
+    /// controllers.
+    ///
+    /// `rows` is clamped to the display height. Returns
+    /// [`PartialAreaError::EmptyArea`] if `rows` doesn't contain at least one
+    /// row of the display. Returns [`PartialAreaError::PoweredOff`] if the
+    /// display is powered off and [`PartialAreaError::Sleeping`] if the display
+    /// is sleeping.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// // Only refresh the top 40 rows of the display
+    /// display.set_partial_area(0..40).unwrap();
+    ///
+    /// display.exit_partial_mode().unwrap();
+    /// ```
+    pub fn set_partial_area(
+        &mut self,
+        rows: Range<u16>,
+    ) -> Result<(), PartialAreaError<DI::Error>> {
+        if !self.state.powered {
+            return Err(PartialAreaError::PoweredOff);
+        }
+        if self.state.sleeping {
+            return Err(PartialAreaError::Sleeping);
+        }
+        let area = self
+            .native_partial_area(rows)
+            .ok_or(PartialAreaError::EmptyArea)?;
+
+        self.enter_partial_mode(area)
+            .map_err(PartialAreaError::Interface)
+    }
+
+    /// Exits partial mode.
+    ///
+    /// The whole display is refreshed again after partial mode was exited.
+    ///
+    /// Returns [`StateError::PoweredOff`] if the display is powered off and
+    /// [`StateError::Sleeping`] if the display is sleeping.
+    pub fn exit_partial_mode(&mut self) -> Result<(), StateError<DI::Error>> {
+        self.check_awake()?;
+        self.di
+            .write_command(dcs::EnterNormalMode)
+            .map_err(StateError::Interface)?;
+        self.state.partial = false;
+
+        Ok(())
+    }
+
+    /// Sets the native partial area and enters partial mode.
+    pub(crate) fn enter_partial_mode(&mut self, area: PartialArea) -> Result<(), DI::Error> {
+        match area {
+            PartialArea::Rows(start, end) => self
+                .di
+                .write_command(dcs::SetPartialRows::new(start, end))?,
+            PartialArea::Columns(start, end) => self
+                .di
+                .write_command(dcs::SetPartialColumns::new(start, end))?,
+        }
+        self.di.write_command(dcs::EnterPartialMode)?;
+        self.partial_area = Some(area);
+        self.state.partial = true;
+
+        Ok(())
+    }
+
+    /// Translates rows of the current orientation into the native partial area.
+    ///
+    /// Returns `None` if the area is empty.
+    fn native_partial_area(&self, rows: Range<u16>) -> Option<PartialArea> {
+        let mapping = MemoryMapping::from(self.options.orientation);
+
+        // Rows of the current orientation are native columns if rows and columns are swapped
+        let (size, offset, reversed) = if mapping.swap_rows_and_columns {
+            let (size, offset) = (self.options.display_size.0, self.options.display_offset.0);
+            (size, offset, mapping.reverse_columns)
+        } else {
+            let (size, offset) = (self.options.display_size.1, self.options.display_offset.1);
+            (size, offset, mapping.reverse_rows)
+        };
+
+        let end = rows.end.min(size);
+        let start = rows.start.min(end);
+        if start >= end {
+            return None;
+        }
+
+        let (start, end) = if reversed {
+            (offset + size - end, offset + size - 1 - start)
+        } else {
+            (offset + start, offset + end - 1)
+        };
+
+        if mapping.swap_rows_and_columns {
+            Some(PartialArea::Columns(start, end))
+        } else {
+            Some(PartialArea::Rows(start, end))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PartialArea, PartialAreaError};
+    use crate::{
+        _mock::new_mock_display,
+        options::{Orientation, Rotation},
+    };
+
+    #[test]
+    fn partial_area_follows_orientation() {
+        let mut display = new_mock_display();
+        assert_eq!(
+            display.native_partial_area(10..50),
+            Some(PartialArea::Rows(10, 49))
+        );
+        assert_eq!(
+            display.native_partial_area(300..400),
+            Some(PartialArea::Rows(300, 319))
+        );
+
+        display
+            .set_orientation(Orientation::new().rotate(Rotation::Deg180))
+            .unwrap();
+        assert_eq!(
+            display.native_partial_area(10..50),
+            Some(PartialArea::Rows(270, 309))
+        );
+
+        display
+            .set_orientation(Orientation::new().rotate(Rotation::Deg90))
+            .unwrap();
+        assert_eq!(
+            display.native_partial_area(0..40),
+            Some(PartialArea::Columns(200, 239))
+        );
+
+        display
+            .set_orientation(Orientation::new().rotate(Rotation::Deg270))
+            .unwrap();
+        assert_eq!(
+            display.native_partial_area(0..40),
+            Some(PartialArea::Columns(0, 39))
+        );
+    }
+
+    #[test]
+    fn empty_partial_area() {
+        let mut display = new_mock_display();
+        assert!(matches!(
+            display.set_partial_area(320..330),
+            Err(PartialAreaError::EmptyArea)
+        ));
+        assert!(matches!(
+            display.set_partial_area(10..10),
+            Err(PartialAreaError::EmptyArea)
+        ));
+        assert!(!display.power_state().partial);
+    }
+}
@@ -0,0 +1,539 @@
+use embedded_hal::{delay::DelayNs, digital::OutputPin};
+
+use crate::{
+    backlight::Backlight,
+    dcs::{self, InterfaceExt},
+    interface::{Interface, InterfacePixelFormat},
+    models::Model,
+    Display, InitError,
+};
+
+/// Default time the supply needs to settle after power on, in microseconds.
+pub(crate) const DEFAULT_POWER_SETTLE_US: u32 = 10_000;
+
+/// Power state of the display controller.
+///
+/// The state is tracked by [`Display`] and updated by all methods which
+/// change the power state of the controller. It can be read by using
+/// [`Display::power_state`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PowerState {
+    /// The supply of the display is switched on.
+    ///
+    /// This is `false` after the display was powered off using
+    /// [`Display::power_off`].
+    pub powered: bool,
+    /// The controller is in sleep mode.
+    pub sleeping: bool,
+    /// The display output is turned on.
+    pub display_on: bool,
+    /// The controller is in idle mode.
+    pub idle: bool,
+    /// The controller is in partial mode.
+    pub partial: bool,
+}
+
+impl PowerState {
+    /// State of the controller after a reset.
+    pub(crate) const RESET: Self = Self {
+        powered: true,
+        sleeping: true,
+        display_on: false,
+        idle: false,
+        partial: false,
+    };
+}
+
+/// Error returned by [`Display::power_off`].
+#[derive(Debug)]
+pub enum PowerError<DI, BL, PWR> {
+    /// Error caused by the display interface.
+    Interface(DI),
+    /// Error caused by the backlight.
+    Backlight(BL),
+    /// Error caused by the power pin's [`OutputPin`] implementation.
+    PowerPin(PWR),
+    /// [`Display::power_off`] was called while the display was already
+    /// powered off.
+    PoweredOff,
+}
+
+/// Error returned by methods which change the power state of the display
+/// controller.
+#[derive(Debug)]
+pub enum StateError<DI> {
+    /// Error caused by the display interface.
+    Interface(DI),
+    /// The display was powered off using [`Display::power_off`].
+    PoweredOff,
+    /// The display is sleeping.
+    ///
+    /// The display needs to be woken up using [`Display::wake`] before the
+    /// power state can be changed.
+    Sleeping,
+}
+
+impl<DI, M, RST, BL, PWR> Display<DI, M, RST, BL, PWR>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+    PWR: OutputPin,
+{
+    /// Powers off the display.
+    ///
+    /// The backlight and display output are turned off and the display enters
+    /// sleep mode before the supply is switched off using the power pin, which
+    /// was set by [`Builder::power_pin`](crate::Builder::power_pin). If no
+    /// power pin was set the display is left in sleep mode.
+    ///
+    /// The content of the framebuffer is lost and the display needs to be
+    /// powered on using [`power_on`](Self::power_on) before it can be used
+    /// again. Until then:
+    ///
+    /// - Drawing operations, like the `DrawTarget` implementation,
+    ///   [`set_pixels`](Self::set_pixels) and
+    ///   [`set_pixels_3bpp`](Self::set_pixels_3bpp), do nothing and return
+    ///   `Ok(())`, because their error type can't report that the display is
+    ///   powered off.
+    /// - Commands which configure the display, like
+    ///   [`set_orientation`](Self::set_orientation),
+    ///   [`set_vertical_scroll_region`](Self::set_vertical_scroll_region) or
+    ///   [`set_tearing_effect`](Self::set_tearing_effect), aren't sent, but
+    ///   the settings are applied when the display is powered on.
+    /// - Methods which change the power state return an error, e.g.
+    ///   [`SleepError::PoweredOff`](crate::SleepError::PoweredOff).
+    ///
+    /// Returns [`PowerError::PoweredOff`] if the display is already powered
+    /// off. The sleep mode commands aren't sent if the display is sleeping.
+    pub fn power_off<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<(), PowerError<DI::Error, BL::Error, PWR::Error>> {
+        if !self.state.powered {
+            return Err(PowerError::PoweredOff);
+        }
+
+        if let Some(backlight) = &mut self.backlight {
+            backlight.set_level(0).map_err(PowerError::Backlight)?;
+        }
+
+        if !self.state.sleeping {
+            self.di
+                .write_command(dcs::SetDisplayOff)
+                .map_err(PowerError::Interface)?;
+            self.state.display_on = false;
+            self.di
+                .write_command(dcs::EnterSleepMode)
+                .map_err(PowerError::Interface)?;
+            delay.delay_us(M::SLEEP_DELAY_US);
+            self.state.sleeping = true;
+        }
+        self.pending = None;
+
+        if let Some(power) = &mut self.power {
+            power.set_low().map_err(PowerError::PowerPin)?;
+        }
+        self.state = PowerState {
+            powered: false,
+            ..PowerState::RESET
+        };
+
+        Ok(())
+    }
+
+    /// Powers on the display after it was powered off.
+    ///
+    /// The supply is switched on and the display is reset and initialized with
+    /// the current options using [`reinit`](Self::reinit), which also restores
+    /// the scroll, page flipping and tearing effect settings, including the
+    /// settings which were changed while the display was powered off. The
+    /// display output is turned on and idle and partial mode are disabled. The
+    /// backlight is kept off until it's turned on using
+    /// [`set_backlight`](Self::set_backlight), after the content was redrawn.
+    pub fn power_on<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<(), InitError<DI::Error, RST::Error, BL::Error, PWR::Error>> {
+        self.reinit(delay)
+    }
+
+    /// Resets and reinitializes the display.
+    ///
+    /// The display is reset using the reset pin, or the Software Reset (0x01)
+    /// command if no reset pin was set, and initialized with the current
+    /// options, including the current orientation. The scroll region, scroll
+    /// offset, page flipping, tearing effect, display output, idle mode and
+    /// partial mode settings are restored afterwards. This can be used to
+    /// recover a display controller which lost its state, e.g. because of a
+    /// brownout or ESD event.
+    ///
+    /// The content of the framebuffer is lost and the display is awake after
+    /// it was reinitialized, even if it was sleeping before. A display which
+    /// was powered off is powered on with the display output turned on and
+    /// idle and partial mode disabled. The backlight is kept off until it's turned on
+    /// using [`set_backlight`](Self::set_backlight), after the content was
+    /// redrawn.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use embedded_graphics::{pixelcolor::Rgb565, prelude::*};
+    ///
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// # let mut delay = mipidsi::_mock::MockDelay;
+    /// display.reinit(&mut delay).unwrap();
+    ///
+    /// display.clear(Rgb565::BLACK).unwrap();
+    /// display.set_backlight(display.backlight_level()).unwrap();
+    /// ```
+    pub fn reinit<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<(), InitError<DI::Error, RST::Error, BL::Error, PWR::Error>> {
+        let scroll_area = self.scroll_area.clone();
+        let scroll_start = self.scroll_start;
+        let tearing_effect = self.tearing_effect;
+        let tear_scanline = self.tear_scanline;
+        let draw_page = self.draw_page;
+        let state = self.state;
+
+        self.initialize(delay)?;
+
+        self.di
+            .write_command(scroll_area.clone())
+            .map_err(InitError::Interface)?;
+        self.scroll_area = scroll_area;
+        self.set_vertical_scroll_offset(scroll_start)
+            .map_err(InitError::Interface)?;
+        self.draw_page = draw_page;
+
+        if let Some(tearing_effect) = tearing_effect {
+            self.set_tearing_effect(tearing_effect)
+                .map_err(InitError::Interface)?;
+        }
+        if let Some(scanline) = tear_scanline {
+            self.set_tear_scanline(scanline)
+                .map_err(InitError::Interface)?;
+        }
+
+        // A powered off display is powered on with the default state
+        if state.powered {
+            if !state.display_on {
+                self.di
+                    .write_command(dcs::SetDisplayOff)
+                    .map_err(InitError::Interface)?;
+                self.state.display_on = false;
+            }
+            if state.idle {
+                self.di
+                    .write_command(dcs::EnterIdleMode)
+                    .map_err(InitError::Interface)?;
+                self.state.idle = true;
+            }
+            if let (true, Some(area)) = (state.partial, self.partial_area) {
+                self.enter_partial_mode(area)
+                    .map_err(InitError::Interface)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns an error if the display is powered off or sleeping.
+    pub(crate) fn check_awake(&self) -> Result<(), StateError<DI::Error>> {
+        if !self.state.powered {
+            Err(StateError::PoweredOff)
+        } else if self.state.sleeping {
+            Err(StateError::Sleeping)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cell::{Cell, RefCell};
+
+    use embedded_graphics_core::{pixelcolor::Rgb565, prelude::*};
+
+    use super::*;
+    use crate::{
+        _mock::{
+            MockBacklight, MockDelay, MockDisplayInterface, MockInterface, MockInterfaceState,
+        },
+        models::ILI9341Rgb565,
+        options::{Orientation, Rotation},
+        Builder, SleepError,
+    };
+
+    /// Output pin which stores its state.
+    struct MockPowerPin<'a>(&'a Cell<bool>);
+
+    impl OutputPin for MockPowerPin<'_> {
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            self.0.set(false);
+            Ok(())
+        }
+
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            self.0.set(true);
+            Ok(())
+        }
+    }
+
+    impl embedded_hal::digital::ErrorType for MockPowerPin<'_> {
+        type Error = core::convert::Infallible;
+    }
+
+    #[test]
+    fn power_cycle() {
+        let powered = Cell::new(false);
+        let mut display = Builder::new(ILI9341Rgb565, MockDisplayInterface)
+            .power_pin(MockPowerPin(&powered))
+            .orientation(Orientation::new().rotate(Rotation::Deg90))
+            .init(&mut MockDelay)
+            .unwrap();
+        assert!(powered.get());
+
+        display.power_off(&mut MockDelay).unwrap();
+        assert!(!powered.get());
+        assert!(display.is_sleeping());
+        assert!(!display.is_display_on());
+
+        display.power_on(&mut MockDelay).unwrap();
+        assert!(powered.get());
+        assert!(!display.is_sleeping());
+        assert!(display.is_display_on());
+        assert_eq!(
+            display.orientation(),
+            Orientation::new().rotate(Rotation::Deg90)
+        );
+    }
+
+    #[test]
+    fn powered_off_display_rejects_commands() {
+        let state = RefCell::new(MockInterfaceState::new());
+        let mut display = Builder::new(ILI9341Rgb565, MockInterface(&state))
+            .init(&mut MockDelay)
+            .unwrap();
+
+        display.power_off(&mut MockDelay).unwrap();
+        assert!(!display.power_state().powered);
+        assert!(matches!(
+            display.power_off(&mut MockDelay),
+            Err(PowerError::PoweredOff)
+        ));
+
+        state.borrow_mut().commands = 0;
+        let orientation = Orientation::new().rotate(Rotation::Deg90);
+        display.set_orientation(orientation).unwrap();
+        display.clear(Rgb565::RED).unwrap();
+        display.set_pixel(0, 0, Rgb565::RED).unwrap();
+        assert!(matches!(
+            display.sleep(&mut MockDelay),
+            Err(SleepError::PoweredOff)
+        ));
+        assert!(matches!(
+            display.wake(&mut MockDelay),
+            Err(SleepError::PoweredOff)
+        ));
+        assert!(matches!(
+            display.set_display_on(true),
+            Err(StateError::PoweredOff)
+        ));
+        assert!(matches!(
+            display.set_idle_mode(true),
+            Err(StateError::PoweredOff)
+        ));
+        assert!(matches!(
+            display.set_partial_area(0..40),
+            Err(crate::PartialAreaError::PoweredOff)
+        ));
+        assert_eq!(state.borrow().commands, 0);
+        assert_eq!(display.orientation(), orientation);
+
+        display.power_on(&mut MockDelay).unwrap();
+        assert!(display.power_state().powered);
+        assert_eq!(display.orientation(), orientation);
+    }
+
+    #[test]
+    fn power_on_applies_settings_changed_while_powered_off() {
+        let state = RefCell::new(MockInterfaceState::new());
+        let mut display = Builder::new(ILI9341Rgb565, MockInterface(&state))
+            .display_size(240, 160)
+            .init(&mut MockDelay)
+            .unwrap();
+        display.power_off(&mut MockDelay).unwrap();
+
+        display
+            .set_tearing_effect(crate::options::TearingEffect::Vertical)
+            .unwrap();
+        display.set_tear_scanline(100).unwrap();
+        display.enable_page_flipping().unwrap();
+        assert_eq!(state.borrow().params_u16(0x44).0, 0);
+
+        display.power_on(&mut MockDelay).unwrap();
+        assert!(display.is_page_flipping_enabled());
+        assert_eq!(display.draw_page, Some(160));
+        assert_eq!(
+            display.tearing_effect,
+            Some(crate::options::TearingEffect::Vertical)
+        );
+        assert_eq!(display.tear_scanline, Some(100));
+        let state = state.borrow();
+        assert_eq!(state.params_u16(0x44).0, 100);
+        assert_eq!(state.params_u16(0x33), (0, 320));
+    }
+
+    #[test]
+    fn power_off_skips_sleep_commands_while_sleeping() {
+        let state = RefCell::new(MockInterfaceState::new());
+        let mut display = Builder::new(ILI9341Rgb565, MockInterface(&state))
+            .init(&mut MockDelay)
+            .unwrap();
+        display.sleep(&mut MockDelay).unwrap();
+
+        state.borrow_mut().commands = 0;
+        display.power_off(&mut MockDelay).unwrap();
+        assert_eq!(state.borrow().commands, 0);
+    }
+
+    #[test]
+    fn failed_sleep_keeps_backlight_on() {
+        let state = RefCell::new(MockInterfaceState::new());
+        let level = Cell::new(0);
+        let mut display = Builder::new(ILI9341Rgb565, MockInterface(&state))
+            .backlight(MockBacklight(&level))
+            .init(&mut MockDelay)
+            .unwrap();
+        display.set_backlight(200).unwrap();
+
+        state.borrow_mut().fail_commands = true;
+        assert!(matches!(
+            display.sleep(&mut MockDelay),
+            Err(SleepError::Interface(_))
+        ));
+        assert!(!display.is_sleeping());
+        assert_eq!(level.get(), 200);
+
+        state.borrow_mut().fail_commands = false;
+        display.sleep(&mut MockDelay).unwrap();
+        assert!(display.is_sleeping());
+        assert_eq!(level.get(), 0);
+    }
+
+    #[test]
+    fn sleeping_display_rejects_state_changes() {
+        let mut display = crate::_mock::new_mock_display();
+        display.sleep(&mut MockDelay).unwrap();
+
+        assert!(matches!(
+            display.set_display_on(false),
+            Err(StateError::Sleeping)
+        ));
+        assert!(matches!(
+            display.set_idle_mode(true),
+            Err(StateError::Sleeping)
+        ));
+        assert!(matches!(
+            display.set_partial_area(0..40),
+            Err(crate::PartialAreaError::Sleeping)
+        ));
+        assert!(matches!(
+            display.exit_partial_mode(),
+            Err(StateError::Sleeping)
+        ));
+        assert_eq!(
+            display.power_state(),
+            PowerState {
+                powered: true,
+                sleeping: true,
+                display_on: true,
+                idle: false,
+                partial: false,
+            }
+        );
+
+        display.wake(&mut MockDelay).unwrap();
+        display.set_idle_mode(true).unwrap();
+        assert!(display.power_state().idle);
+    }
+
+    #[test]
+    fn power_state_is_tracked() {
+        let mut display = crate::_mock::new_mock_display();
+        assert_eq!(
+            display.power_state(),
+            PowerState {
+                powered: true,
+                sleeping: false,
+                display_on: true,
+                idle: false,
+                partial: false,
+            }
+        );
+
+        display.set_idle_mode(true).unwrap();
+        display.set_partial_area(0..40).unwrap();
+        assert!(display.power_state().idle);
+        assert!(display.power_state().partial);
+
+        assert!(matches!(
+            display.wake(&mut MockDelay),
+            Err(crate::SleepError::NotSleeping)
+        ));
+        display.sleep(&mut MockDelay).unwrap();
+        assert!(matches!(
+            display.sleep(&mut MockDelay),
+            Err(crate::SleepError::AlreadySleeping)
+        ));
+        display.wake(&mut MockDelay).unwrap();
+
+        display.power_off(&mut MockDelay).unwrap();
+        display.power_on(&mut MockDelay).unwrap();
+        assert!(!display.power_state().idle);
+        assert!(!display.power_state().partial);
+    }
+
+    #[test]
+    fn reinit_restores_settings() {
+        let mut display = crate::_mock::new_mock_display();
+        display.set_vertical_scroll_region(10, 20).unwrap();
+        display.set_vertical_scroll_offset(30).unwrap();
+        display
+            .set_tearing_effect(crate::options::TearingEffect::Vertical)
+            .unwrap();
+        display.set_display_on(false).unwrap();
+        display.set_idle_mode(true).unwrap();
+        display.set_partial_area(0..40).unwrap();
+        display.sleep(&mut MockDelay).unwrap();
+
+        display.reinit(&mut MockDelay).unwrap();
+        assert_eq!(
+            display.power_state(),
+            PowerState {
+                powered: true,
+                sleeping: false,
+                display_on: false,
+                idle: true,
+                partial: true,
+            }
+        );
+        assert_eq!(
+            display.partial_area,
+            Some(crate::partial::PartialArea::Rows(0, 39))
+        );
+        assert_eq!(display.scroll_area, dcs::SetScrollArea::new(10, 290, 20));
+        assert_eq!(display.scroll_start, 30);
+        assert_eq!(
+            display.tearing_effect,
+            Some(crate::options::TearingEffect::Vertical)
+        );
+        assert_eq!(display.tear_scanline, None);
+    }
+}
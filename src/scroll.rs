@@ -0,0 +1,186 @@
+use embedded_hal::digital::OutputPin;
+
+use crate::{
+    backlight::Backlight,
+    dcs,
+    interface::{Interface, InterfacePixelFormat},
+    models::Model,
+    options::{MemoryMapping, ScrollAxis},
+    Display,
+};
+
+/// Error returned by the orientation aware scrolling methods.
+#[derive(Debug)]
+pub enum ScrollError<DI> {
+    /// Error caused by the display interface.
+    Interface(DI),
+    /// The requested axis can't be scrolled in the current orientation.
+    ///
+    /// Hardware scrolling is only supported along the vertical axis of the
+    /// default display orientation, which becomes the horizontal axis in
+    /// 90° and 270° rotated orientations.
+    UnsupportedAxis,
+    /// The fixed areas are larger than the display.
+    FixedAreaTooLarge,
+}
+
+impl<DI, M, RST, BL, PWR> Display<DI, M, RST, BL, PWR>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+    PWR: OutputPin,
+{
+    /// Sets the scroll region along an axis of the current orientation.
+    ///
+    /// `start_fixed_area` and `end_fixed_area` define the number of pixels at
+    /// the start (top or left) and end (bottom or right) of the display which
+    /// won't be affected by scrolling. The values are translated into the
+    /// native scroll region of the display, taking the current orientation and
+    /// display offset into account.
+    ///
+    /// Returns [`ScrollError::UnsupportedAxis`] if the display can't be scrolled
+    /// along `axis` in the current orientation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mipidsi::options::{Orientation, Rotation, ScrollAxis};
+    ///
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// display.set_orientation(Orientation::new().rotate(Rotation::Deg90)).unwrap();
+    ///
+    /// // Landscape orientation can only be scrolled horizontally
+    /// display.set_scroll_region(ScrollAxis::Horizontal, 0, 0).unwrap();
+    /// display.set_scroll_offset(ScrollAxis::Horizontal, 10).unwrap();
+    /// assert!(display.set_scroll_region(ScrollAxis::Vertical, 0, 0).is_err());
+    /// ```
+    pub fn set_scroll_region(
+        &mut self,
+        axis: ScrollAxis,
+        start_fixed_area: u16,
+        end_fixed_area: u16,
+    ) -> Result<(), ScrollError<DI::Error>> {
+        let reversed = self.native_scroll_direction(axis)?;
+
+        let (top_fixed_area, bottom_fixed_area) = if reversed {
+            (end_fixed_area, start_fixed_area)
+        } else {
+            (start_fixed_area, end_fixed_area)
+        };
+
+        // The fixed areas are extended to the rows outside of the display area
+        let offset = self.options.display_offset.1;
+        let outside = M::FRAMEBUFFER_SIZE.1 - self.options.display_size.1 - offset;
+        self.set_vertical_scroll_region(offset + top_fixed_area, outside + bottom_fixed_area)
+            .map_err(ScrollError::Interface)
+    }
+
+    /// Sets the scroll offset along an axis of the current orientation.
+    ///
+    /// The content of the scroll region is shifted towards the start (top or
+    /// left) of the display by `offset` pixels. The scroll region must be set up
+    /// using [`set_scroll_region`](Self::set_scroll_region) before this method
+    /// is used.
+    ///
+    /// Returns [`ScrollError::UnsupportedAxis`] if the display can't be scrolled
+    /// along `axis` in the current orientation.
+    pub fn set_scroll_offset(
+        &mut self,
+        axis: ScrollAxis,
+        offset: u16,
+    ) -> Result<(), ScrollError<DI::Error>> {
+        let reversed = self.native_scroll_direction(axis)?;
+
+        let dcs::SetScrollArea { tfa, vsa, .. } = self.scroll_area;
+        let offset = offset.checked_rem(vsa).unwrap_or(0);
+        let offset = if reversed && offset != 0 {
+            vsa - offset
+        } else {
+            offset
+        };
+
+        self.set_vertical_scroll_offset(tfa + offset)
+            .map_err(ScrollError::Interface)
+    }
+
+    /// Returns `true` if scrolling along `axis` is reversed relative to the
+    /// native scroll direction.
+    fn native_scroll_direction(&self, axis: ScrollAxis) -> Result<bool, ScrollError<DI::Error>> {
+        let mapping = MemoryMapping::from(self.options.orientation);
+
+        // Native rows are mapped to the horizontal axis if rows and columns are swapped
+        let native_axis = if mapping.swap_rows_and_columns {
+            ScrollAxis::Horizontal
+        } else {
+            ScrollAxis::Vertical
+        };
+        if axis != native_axis {
+            return Err(ScrollError::UnsupportedAxis);
+        }
+
+        Ok(mapping.reverse_rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        _mock::new_mock_display,
+        options::{Orientation, Rotation},
+    };
+
+    #[test]
+    fn scroll_region_follows_orientation() {
+        let mut display = new_mock_display();
+
+        display
+            .set_scroll_region(ScrollAxis::Vertical, 10, 30)
+            .unwrap();
+        assert_eq!(display.scroll_area, dcs::SetScrollArea::new(10, 280, 30));
+
+        display
+            .set_orientation(Orientation::new().rotate(Rotation::Deg180))
+            .unwrap();
+        display
+            .set_scroll_region(ScrollAxis::Vertical, 10, 30)
+            .unwrap();
+        assert_eq!(display.scroll_area, dcs::SetScrollArea::new(30, 280, 10));
+
+        assert!(matches!(
+            display.set_scroll_region(ScrollAxis::Horizontal, 0, 0),
+            Err(ScrollError::UnsupportedAxis)
+        ));
+
+        display
+            .set_orientation(Orientation::new().rotate(Rotation::Deg270))
+            .unwrap();
+        assert!(display
+            .set_scroll_region(ScrollAxis::Horizontal, 0, 20)
+            .is_ok());
+        assert_eq!(display.scroll_area, dcs::SetScrollArea::new(20, 300, 0));
+    }
+
+    #[test]
+    fn reversed_offsets() {
+        let mut display = new_mock_display();
+        display
+            .set_orientation(Orientation::new().rotate(Rotation::Deg90))
+            .unwrap();
+        assert!(matches!(
+            display.native_scroll_direction(ScrollAxis::Horizontal),
+            Ok(false)
+        ));
+
+        display
+            .set_orientation(Orientation::new().rotate(Rotation::Deg270))
+            .unwrap();
+        assert!(matches!(
+            display.native_scroll_direction(ScrollAxis::Horizontal),
+            Ok(true)
+        ));
+    }
+}
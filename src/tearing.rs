@@ -0,0 +1,259 @@
+use embedded_hal::{
+    delay::DelayNs,
+    digital::{InputPin, OutputPin},
+};
+
+use crate::{
+    backlight::Backlight,
+    interface::{Interface, InterfacePixelFormat},
+    models::Model,
+    options::TearingEffect,
+    Display,
+};
+
+/// Interval between two reads of the TE signal, in microseconds.
+const POLL_INTERVAL_US: u32 = 10;
+/// Maximum time to wait for the next rising edge of the TE signal, in
+/// microseconds, which is longer than the frame period of all supported models.
+const VBLANK_TIMEOUT_US: u32 = 100_000;
+
+/// Error returned by [`Display::wait_for_vblank`] and
+/// [`Display::measure_frame_period`].
+#[derive(Debug)]
+pub enum TearingError<TE> {
+    /// Error caused by the TE pin's [`InputPin`] implementation.
+    Pin(TE),
+    /// The TE output wasn't enabled using
+    /// [`Display::set_tearing_effect`].
+    Disabled,
+    /// No rising edge of the TE signal was detected within 100 ms.
+    ///
+    /// This usually means that the TE pin isn't connected or that the display
+    /// is sleeping or powered off.
+    Timeout,
+}
+
+impl<DI, M, RST, BL, PWR> Display<DI, M, RST, BL, PWR>
+where
+    DI: Interface,
+    M: Model,
+    M::ColorFormat: InterfacePixelFormat<DI::Word>,
+    RST: OutputPin,
+    BL: Backlight,
+    PWR: OutputPin,
+{
+    /// Waits for the start of the next vertical blanking period.
+    ///
+    /// `te` must be connected to the TE output of the display controller and
+    /// the output must be enabled using
+    /// [`set_tearing_effect`](Self::set_tearing_effect) with
+    /// [`TearingEffect::Vertical`].
+    /// This method polls the TE signal every 10 µs, using `delay`, until the
+    /// next rising edge, which means that drawing operations started directly
+    /// after this method returns won't be visible until the frame is complete,
+    /// as long as the data is written faster than the display is refreshed.
+    ///
+    /// Returns [`TearingError::Disabled`] if the TE output isn't enabled and
+    /// [`TearingError::Timeout`] if no rising edge was detected within 100 ms.
+    ///
+    /// The scanline which triggers the TE signal can be changed using
+    /// [`set_tear_scanline`](Self::set_tear_scanline).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use embedded_graphics::{pixelcolor::Rgb565, prelude::*};
+    /// use mipidsi::options::TearingEffect;
+    ///
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// # let mut te = mipidsi::_mock::MockInputPin::default();
+    /// # let mut delay = mipidsi::_mock::MockDelay;
+    /// display.set_tearing_effect(TearingEffect::Vertical).unwrap();
+    ///
+    /// display.wait_for_vblank(&mut te, &mut delay).unwrap();
+    /// display.clear(Rgb565::BLACK).unwrap();
+    /// ```
+    pub fn wait_for_vblank<TE, D>(
+        &mut self,
+        te: &mut TE,
+        delay: &mut D,
+    ) -> Result<(), TearingError<TE::Error>>
+    where
+        TE: InputPin,
+        D: DelayNs,
+    {
+        if matches!(self.tearing_effect, None | Some(TearingEffect::Off)) {
+            return Err(TearingError::Disabled);
+        }
+
+        let mut polls = VBLANK_TIMEOUT_US / POLL_INTERVAL_US;
+        let mut poll = |high: bool| -> Result<(), TearingError<TE::Error>> {
+            while te.is_high().map_err(TearingError::Pin)? == high {
+                polls = polls.checked_sub(1).ok_or(TearingError::Timeout)?;
+                delay.delay_us(POLL_INTERVAL_US);
+            }
+            Ok(())
+        };
+        poll(true)?;
+        poll(false)
+    }
+
+    /// Measures the frame period using the TE signal.
+    ///
+    /// Returns the average time between the rising edges of the TE signal over
+    /// `frames` frames in microseconds. The frame rate in Hz can be calculated
+    /// by dividing `1_000_000` by the returned value.
+    ///
+    /// The TE output must be enabled in the same way as for
+    /// [`wait_for_vblank`](Self::wait_for_vblank), which is also used to wait
+    /// for the rising edges and returns the same errors. `now_us` must return
+    /// the current time in microseconds of a monotonic clock, which is read at
+    /// the first and the last rising edge.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mipidsi::options::TearingEffect;
+    ///
+    /// # let mut display = mipidsi::_mock::new_mock_display();
+    /// # let mut te = mipidsi::_mock::MockInputPin::default();
+    /// # let mut delay = mipidsi::_mock::MockDelay;
+    /// # let timer = core::cell::Cell::new(0u64);
+    /// # let now_us = || { timer.set(timer.get() + 16_667); timer.get() };
+    /// display.set_tearing_effect(TearingEffect::Vertical).unwrap();
+    ///
+    /// let period = display.measure_frame_period(&mut te, &mut delay, now_us, 10).unwrap();
+    /// let frame_rate = 1_000_000 / period;
+    /// ```
+    pub fn measure_frame_period<TE, D, F>(
+        &mut self,
+        te: &mut TE,
+        delay: &mut D,
+        mut now_us: F,
+        frames: u16,
+    ) -> Result<u32, TearingError<TE::Error>>
+    where
+        TE: InputPin,
+        D: DelayNs,
+        F: FnMut() -> u64,
+    {
+        self.wait_for_vblank(te, delay)?;
+        let start = now_us();
+
+        for _ in 0..frames {
+            self.wait_for_vblank(te, delay)?;
+        }
+        let elapsed = now_us().saturating_sub(start);
+
+        let period = elapsed / u64::from(frames.max(1));
+        Ok(u32::try_from(period).unwrap_or(u32::MAX))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::Cell;
+
+    use crate::_mock::{new_mock_display, MockDelay};
+
+    /// TE signal which is high for 2 and low for 8 reads.
+    ///
+    /// Every read advances the clock by 100 µs.
+    struct TePin<'a> {
+        reads: u32,
+        clock: &'a Cell<u64>,
+    }
+
+    impl InputPin for TePin<'_> {
+        fn is_high(&mut self) -> Result<bool, Self::Error> {
+            self.reads += 1;
+            self.clock.set(self.clock.get() + 100);
+            Ok(self.reads % 10 < 2)
+        }
+
+        fn is_low(&mut self) -> Result<bool, Self::Error> {
+            self.is_high().map(|high| !high)
+        }
+    }
+
+    impl embedded_hal::digital::ErrorType for TePin<'_> {
+        type Error = core::convert::Infallible;
+    }
+
+    /// TE signal which is stuck at a fixed level.
+    struct StuckPin(bool);
+
+    impl InputPin for StuckPin {
+        fn is_high(&mut self) -> Result<bool, Self::Error> {
+            Ok(self.0)
+        }
+
+        fn is_low(&mut self) -> Result<bool, Self::Error> {
+            Ok(!self.0)
+        }
+    }
+
+    impl embedded_hal::digital::ErrorType for StuckPin {
+        type Error = core::convert::Infallible;
+    }
+
+    #[test]
+    fn waits_for_rising_edge() {
+        let mut display = new_mock_display();
+        display.set_tearing_effect(TearingEffect::Vertical).unwrap();
+        let clock = Cell::new(0);
+        let mut te = TePin {
+            reads: 0,
+            clock: &clock,
+        };
+
+        display.wait_for_vblank(&mut te, &mut MockDelay).unwrap();
+        assert_eq!(te.reads, 10);
+    }
+
+    #[test]
+    fn requires_tearing_effect_output() {
+        let mut display = new_mock_display();
+        assert!(matches!(
+            display.wait_for_vblank(&mut StuckPin(false), &mut MockDelay),
+            Err(TearingError::Disabled)
+        ));
+
+        display.set_tearing_effect(TearingEffect::Off).unwrap();
+        assert!(matches!(
+            display.wait_for_vblank(&mut StuckPin(false), &mut MockDelay),
+            Err(TearingError::Disabled)
+        ));
+    }
+
+    #[test]
+    fn times_out_without_rising_edge() {
+        let mut display = new_mock_display();
+        display.set_tearing_effect(TearingEffect::Vertical).unwrap();
+
+        for level in [false, true] {
+            assert!(matches!(
+                display.wait_for_vblank(&mut StuckPin(level), &mut MockDelay),
+                Err(TearingError::Timeout)
+            ));
+        }
+    }
+
+    #[test]
+    fn measures_frame_period() {
+        let mut display = new_mock_display();
+        display.set_tearing_effect(TearingEffect::Vertical).unwrap();
+        let clock = Cell::new(0);
+        let mut te = TePin {
+            reads: 0,
+            clock: &clock,
+        };
+
+        // Every frame takes 10 reads
+        let period = display
+            .measure_frame_period(&mut te, &mut MockDelay, || clock.get(), 4)
+            .unwrap();
+        assert_eq!(period, 1_000);
+    }
+}